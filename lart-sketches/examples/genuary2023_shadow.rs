@@ -61,9 +61,7 @@ fn main() {
             pts.push(V::in_rect(&mut doc, &bbox));
         }
 
-        for g in voronoi(&pts, &bbox).paths {
-            let g = Geometry::from(g);
-
+        for g in voronoi(&pts, &bbox).cells {
             let a = doc.gen_range(0.0..=TAU);
             let step = doc.gen_range(2..=10) as f64 / 2.0;
 
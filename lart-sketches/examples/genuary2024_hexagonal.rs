@@ -41,7 +41,7 @@ fn main() {
 
         for y in bbox.top() as i32 - 1..=bbox.bottom() as i32 + 1 {
             for x in bbox.left() as i32 - 1..=bbox.right() as i32 + 1 {
-                let alive = world.get(&(x, y)).is_some();
+                let alive = world.contains(&(x, y));
 
                 let bit = [
                     (x - 1, y),
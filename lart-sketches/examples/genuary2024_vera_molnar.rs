@@ -1,4 +1,4 @@
-use std::{collections::HashSet, f64::INFINITY};
+use std::collections::HashSet;
 
 use lart::*;
 
@@ -76,7 +76,7 @@ pub fn main() {
 
             let lp = p.last().unwrap();
 
-            let mut mind = INFINITY;
+            let mut mind = f64::INFINITY;
             let mut closest = 0;
             let mut rev = false;
             for (i, pp) in paths.iter().enumerate() {
@@ -51,10 +51,7 @@ fn main() {
     // this is not correct for non-convex polygons, but hey, it's a sketch
     let visible_faces = side_faces
         .iter()
-        .filter(|f| {
-            !parms.occult
-                || (Geometry::from((**f).clone()) & Geometry::from(front_face.clone())).is_empty()
-        })
+        .filter(|f| !parms.occult || (*f & &front_face).is_empty())
         .cloned()
         .collect::<Vec<_>>();
 
@@ -90,11 +87,7 @@ fn main() {
 
     for f in visible_faces {
         if parms.shading {
-            let tex = parallel_hatch(
-                &Geometry::from(f.clone()),
-                doc.gen_range(0.0..=TAU),
-                doc.gen_range(0.8..=1.2),
-            );
+            let tex = parallel_hatch(&f, doc.gen_range(0.0..=TAU), doc.gen_range(0.8..=1.2));
             doc.geometry(tex);
         }
         doc.geometry(f);
@@ -1,10 +1,14 @@
 #include "rust/cxx.h"
 
+#include <cstdint>
 #include <memory>
 
 struct V;
 struct Path;
 struct Geometry;
+struct Polygon;
+struct BufferOptions;
+enum class FillRule : std::uint8_t;
 
 class Clipper
 {
@@ -13,6 +17,7 @@ public:
 
     void add_subject(Path const & /*subject*/);
     void add_clip(Path const & /*polygon*/);
+    void set_fill_rule(FillRule /*fill_rule*/);
 
     Geometry union_();
     Geometry intersection();
@@ -26,4 +31,9 @@ private:
 
 std::unique_ptr<Clipper> new_clipper();
 
-Geometry buffer(Geometry const & /*geo*/, double /*delta*/);
+Geometry buffer(Geometry const & /*geo*/, double /*delta*/, BufferOptions const & /*opts*/);
+
+rust::Vec<Polygon> polygons(Geometry const & /*geo*/, FillRule /*fill_rule*/);
+
+Geometry minkowski_sum(Path const & /*pattern*/, Geometry const & /*geo*/);
+Geometry minkowski_diff(Path const & /*pattern*/, Geometry const & /*geo*/);
@@ -0,0 +1,252 @@
+use std::collections::{HashMap, HashSet};
+
+use crate::{linterp, v, Geometry, Grid, Path, Rect, V};
+
+/// Extract the contour lines of the scalar field sampled by the given Grid at
+/// each of the given levels using the [Marching Squares][0] algorithm.
+///
+/// The samples of the Grid are evenly spread over `bounds` so that the first
+/// and the last ones lie on its corners.
+///
+/// The contours are joined into continuous paths that are closed unless they
+/// reach the border of the Grid. The paths keep the area where the field is
+/// greater than or equal to the level on their left, that is they wind
+/// counter-clockwise around the peaks in a coordinate system where y points
+/// up. Saddle cells are disambiguated by sampling the field in their center as
+/// the average of their corners.
+///
+/// [0]: https://en.wikipedia.org/wiki/Marching_squares
+///
+/// ```rust
+/// # use lart::*;
+/// // a cone with the tip at the center
+/// let mut grid = Grid::new(0.0, 21, 21);
+/// for (x, y) in grid.indices() {
+///     grid[(x, y)] = -v(x as f64, y as f64).dist(v(10, 10)) / 2.0;
+/// }
+/// let bounds = Rect::with_dimensions(v(0, 0), 10.0, 10.0);
+///
+/// let isolines = contours(&grid, &bounds, &[-3.0, -6.0]);
+/// assert_eq!(isolines.len(), 2);
+///
+/// let (level, circle) = &isolines[0];
+/// assert_eq!(*level, -3.0);
+/// assert_eq!(circle.paths().len(), 1);
+/// assert!(circle.paths()[0].is_closed());
+/// assert!(circle.paths()[0].signed_area() > 0.0);
+/// assert!(circle.paths()[0].iter().all(|p| (p.dist(v(5, 5)) - 3.0).abs() < 0.05));
+///
+/// // the circle of radius 6 is cut by the border into 4 open arcs
+/// let (_, arcs) = &isolines[1];
+/// assert_eq!(arcs.paths().len(), 4);
+/// assert!(arcs.paths().iter().all(|p| !p.is_closed()));
+/// ```
+pub fn contours(grid: &Grid<f64>, bounds: &Rect, levels: &[f64]) -> Vec<(f64, Geometry)> {
+    let field = Field {
+        grid,
+        bounds,
+        closed: false,
+    };
+
+    levels.iter().map(|&l| (l, field.isolines(l))).collect()
+}
+
+/// Return the filled bands of the scalar field sampled by the given Grid
+/// between each of the given levels and the next one, the last band covers
+/// all the area where the field is greater than or equal to the last level.
+///
+/// The levels are expected to be sorted in increasing order.
+///
+/// Each band is made of the closed contours of its lower level, clipped to
+/// `bounds`, and of the reversed contours of its upper level, so that the
+/// holes wind clockwise and `Geometry::polygons` can turn it into proper
+/// Polygons.
+///
+/// See `contours` for more details.
+///
+/// ```rust
+/// # use lart::*;
+/// // a ramp going from 0 on the left to 10 on the right
+/// let mut grid = Grid::new(0.0, 11, 11);
+/// for (x, y) in grid.indices() {
+///     grid[(x, y)] = x as f64;
+/// }
+/// let bounds = Rect::with_dimensions(v(0, 0), 20.0, 10.0);
+///
+/// let bands = filled_contours(&grid, &bounds, &[2.5, 5.0]);
+/// assert_eq!(bands.len(), 2);
+/// assert!((bands[0].1.signed_area() - 50.0).abs() < 1e-9);
+/// assert!((bands[1].1.signed_area() - 100.0).abs() < 1e-9);
+///
+/// assert!(bands[0].1.contains(v(7, 5)));
+/// assert!(!bands[0].1.contains(v(12, 5)));
+/// assert!(bands[1].1.contains(v(12, 5)));
+/// ```
+pub fn filled_contours(grid: &Grid<f64>, bounds: &Rect, levels: &[f64]) -> Vec<(f64, Geometry)> {
+    let field = Field {
+        grid,
+        bounds,
+        closed: true,
+    };
+
+    let regions = levels
+        .iter()
+        .map(|&l| (l, field.isolines(l)))
+        .collect::<Vec<_>>();
+
+    let mut bands = Vec::with_capacity(regions.len());
+    for (i, (level, region)) in regions.iter().enumerate() {
+        let mut band = region.clone();
+        if let Some((_, upper)) = regions.get(i + 1) {
+            band.push_paths(upper.paths().iter().map(|p| {
+                let mut p = p.clone();
+                p.reverse();
+                p
+            }));
+        }
+
+        bands.push((*level, band));
+    }
+
+    bands
+}
+
+/// An edge between two consecutive samples of the Grid, either horizontal
+/// going right or vertical going down from the given sample.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash, PartialOrd, Ord)]
+enum Edge {
+    H(isize, isize),
+    V(isize, isize),
+}
+
+struct Field<'a> {
+    grid: &'a Grid<f64>,
+    bounds: &'a Rect,
+
+    /// Whether the Grid should be considered surrounded by samples lower than
+    /// any level so that all the contours are closed.
+    closed: bool,
+}
+
+impl Field<'_> {
+    fn isolines(&self, level: f64) -> Geometry {
+        let (w, h) = (self.grid.width() as isize, self.grid.height() as isize);
+        let (cells_x, cells_y) = if self.closed {
+            (-1..w, -1..h)
+        } else {
+            (0..w - 1, 0..h - 1)
+        };
+
+        // map each crossing to the next one along its contour, each crossing
+        // starts and ends at most one segment because the segments of each
+        // cell keep the inside on their left
+        let mut next = HashMap::new();
+        for y in cells_y {
+            for x in cells_x.clone() {
+                let corners = [(x, y), (x + 1, y), (x + 1, y + 1), (x, y + 1)];
+                let edges = [
+                    Edge::H(x, y),
+                    Edge::V(x + 1, y),
+                    Edge::H(x, y + 1),
+                    Edge::V(x, y),
+                ];
+                let values = corners.map(|(x, y)| self.value(x, y));
+                let inside = values.map(|v| v >= level);
+
+                let exit = |k: usize| inside[k] && !inside[(k + 1) % 4];
+                let entry = |k: usize| !inside[k] && inside[(k + 1) % 4];
+
+                // in saddle cells connect the inside corners if the center
+                // is inside too and keep them separate otherwise
+                let center_inside = values.iter().sum::<f64>() / 4.0 >= level;
+
+                for k in (0..4).filter(|&k| exit(k)) {
+                    let j = if center_inside {
+                        (1..4).map(|d| (k + d) % 4).find(|&j| entry(j))
+                    } else {
+                        (1..4).map(|d| (k + 4 - d) % 4).find(|&j| entry(j))
+                    };
+
+                    if let Some(j) = j {
+                        next.insert(edges[k], edges[j]);
+                    }
+                }
+            }
+        }
+
+        // open contours start from the crossings that do not have a
+        // predecessor, all the others are closed loops
+        let targets = next.values().copied().collect::<HashSet<_>>();
+        let mut starts = next.keys().copied().collect::<Vec<_>>();
+        starts.sort_unstable();
+        starts.sort_by_key(|e| targets.contains(e));
+
+        let mut geo = Geometry::new();
+        for start in starts {
+            if !next.contains_key(&start) {
+                continue;
+            }
+
+            let mut path = Path::new();
+            path.push(self.crossing(start, level));
+
+            let mut cur = start;
+            while let Some(n) = next.remove(&cur) {
+                path.push(self.crossing(n, level));
+                cur = n;
+            }
+
+            path.dedup();
+            if path.len() > 1 {
+                geo.push_path(path);
+            }
+        }
+
+        geo
+    }
+
+    fn value(&self, x: isize, y: isize) -> f64 {
+        if x < 0 || y < 0 {
+            return f64::NEG_INFINITY;
+        }
+
+        self.grid
+            .get(x as usize, y as usize)
+            .filter(|_| (x as usize) < self.grid.width())
+            .copied()
+            .unwrap_or(f64::NEG_INFINITY)
+    }
+
+    fn position(&self, x: isize, y: isize) -> V {
+        let (w, h) = (self.grid.width().max(2), self.grid.height().max(2));
+        self.bounds.min()
+            + v(
+                self.bounds.width() * x as f64 / (w - 1) as f64,
+                self.bounds.height() * y as f64 / (h - 1) as f64,
+            )
+    }
+
+    fn crossing(&self, e: Edge, level: f64) -> V {
+        let (a, b) = match e {
+            Edge::H(x, y) => ((x, y), (x + 1, y)),
+            Edge::V(x, y) => ((x, y), (x, y + 1)),
+        };
+
+        let (va, vb) = (self.value(a.0, a.1), self.value(b.0, b.1));
+
+        // the samples outside the Grid are only there to close the contours
+        // along its border
+        if va == f64::NEG_INFINITY {
+            return self.position(b.0, b.1);
+        }
+        if vb == f64::NEG_INFINITY {
+            return self.position(a.0, a.1);
+        }
+
+        linterp(
+            self.position(a.0, a.1),
+            self.position(b.0, b.1),
+            (level - va) / (vb - va),
+        )
+    }
+}
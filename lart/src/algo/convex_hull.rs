@@ -1,37 +1,138 @@
-use crate::{F64Key, Path, V};
+use std::collections::{HashMap, HashSet};
 
-/// An implementation of the [Gift Wrapping Algorithm][0] to return the Convex
-/// Hull of a set of points.
+use crate::{Geometry, Path, V};
+
+/// An implementation of the [Monotone Chain Algorithm][0] to return the Convex
+/// Hull of a set of points in O(n log n).
+///
+/// The hull is returned as a closed Path winding counter-clockwise in a
+/// coordinate system where y points up. Duplicated points and points lying on
+/// the edges of the hull are not part of the result.
+///
+/// [0]: https://en.wikibooks.org/wiki/Algorithm_Implementation/Geometry/Convex_hull/Monotone_chain
+///
+/// ```rust
+/// # use lart::*;
+/// let pts = [v(5, 5), v(10, 10), v(0, 0), v(10, 0), v(5, 0), v(0, 10), v(10, 0)];
+/// let hull = convex_hull(&pts);
+/// assert_eq!(hull, polygon!(v(0, 0), v(10, 0), v(10, 10), v(0, 10)));
 ///
-/// [0]: https://en.wikipedia.org/wiki/Gift_wrapping_algorithm
+/// assert_eq!(convex_hull(&[v(0, 0), v(5, 5), v(10, 10)]), polygon!(v(0, 0), v(10, 10)));
+/// ```
 pub fn convex_hull(points: &[V]) -> Path {
-    if points.len() <= 3 {
-        return Path::from(points.iter().cloned()).closed();
+    let mut points = points.to_vec();
+    points.sort_by(|a, b| a.x.total_cmp(&b.x).then(a.y.total_cmp(&b.y)));
+    points.dedup();
+
+    if points.len() <= 2 {
+        return Path::from(points).closed();
+    }
+
+    let mut hull: Vec<V> = Vec::with_capacity(points.len() + 1);
+
+    // lower hull from left to right and then upper hull from right to left,
+    // in both cases only counter-clockwise turns are allowed
+    for &p in &points {
+        while hull.len() >= 2 && p.orient(hull[hull.len() - 2], hull[hull.len() - 1]) <= 0.0 {
+            hull.pop();
+        }
+        hull.push(p);
     }
 
-    let mut hull = vec![];
+    let lower_len = hull.len();
+    for &p in points.iter().rev().skip(1) {
+        while hull.len() > lower_len && p.orient(hull[hull.len() - 2], hull[hull.len() - 1]) <= 0.0
+        {
+            hull.pop();
+        }
+        hull.push(p);
+    }
 
-    let mut on_hull = *points
+    Path::from(hull)
+}
+
+/// Return the [alpha shape][0] of the given set of points which is a concave
+/// outline that can have holes and multiple components.
+///
+/// The alpha shape is built out of the triangles of the Delaunay triangulation
+/// whose circumradius is at most `alpha`. The smaller `alpha` is the tighter the
+/// outline, while as `alpha` grows the result tends to the convex hull.
+///
+/// Exterior rings wind counter-clockwise and holes clockwise in a coordinate
+/// system where y points up, so that the result can be used directly in
+/// boolean operations.
+///
+/// [0]: https://en.wikipedia.org/wiki/Alpha_shape
+///
+/// ```rust
+/// # use lart::*;
+/// // L shaped cloud of points
+/// let pts = (0..=10)
+///     .flat_map(|x| (0..=10).map(move |y| v(x, y)))
+///     .filter(|p| p.x <= 4.0 || p.y <= 4.0)
+///     .collect::<Vec<_>>();
+///
+/// // the L itself plus a small triangle cutting its inner corner
+/// let hull = concave_hull(&pts, 1.0);
+/// assert_eq!(hull.paths().len(), 1);
+/// assert!((hull.paths()[0].area() - 64.5).abs() < 1e-9);
+///
+/// assert!((convex_hull(&pts).area() - 82.0).abs() < 1e-9);
+/// ```
+pub fn concave_hull(points: &[V], alpha: f64) -> Geometry {
+    let pts = points
         .iter()
-        .min_by_key(|p| (F64Key(p.x), F64Key(p.y)))
-        .unwrap();
+        .map(|p| delaunator::Point { x: p.x, y: p.y })
+        .collect::<Vec<_>>();
+    let tri = delaunator::triangulate(&pts);
+
+    // the directed edges of the kept triangles cancel out with their twins
+    // when the triangles are adjacent, only the boundary edges are left
+    let mut edges = HashSet::new();
+    for t in tri.triangles.chunks_exact(3) {
+        let (mut a, b, mut c) = (t[0], t[1], t[2]);
+        let (pa, pb, pc) = (points[a], points[b], points[c]);
+        let area2 = pc.orient(pa, pb);
+        if area2 == 0.0 {
+            continue;
+        }
 
-    loop {
-        hull.push(on_hull);
-        let mut endpoint = points[0];
+        let circumradius = pa.dist(pb) * pb.dist(pc) * pc.dist(pa) / (2.0 * area2.abs());
+        if circumradius > alpha {
+            continue;
+        }
+
+        if area2 < 0.0 {
+            std::mem::swap(&mut a, &mut c);
+        }
 
-        for p in points {
-            if endpoint == on_hull || p.orient(on_hull, endpoint) > 0.0 {
-                endpoint = *p;
+        for (s, e) in [(a, b), (b, c), (c, a)] {
+            if !edges.remove(&(e, s)) {
+                edges.insert((s, e));
             }
         }
+    }
 
-        if endpoint == hull[0] {
-            break;
+    let mut outgoing: HashMap<usize, Vec<usize>> = HashMap::new();
+    let mut starts = edges.into_iter().collect::<Vec<_>>();
+    starts.sort_unstable();
+    for &(s, e) in &starts {
+        outgoing.entry(s).or_default().push(e);
+    }
+
+    let mut geo = Geometry::new();
+    for (s, _) in starts {
+        let mut ring = Path::new();
+        let mut cur = s;
+        while let Some(next) = outgoing.get_mut(&cur).and_then(Vec::pop) {
+            ring.push(points[cur]);
+            cur = next;
         }
 
-        on_hull = endpoint;
+        if !ring.is_empty() {
+            geo.push_path(ring.closed());
+        }
     }
 
-    Path::from(hull).closed()
+    geo
 }
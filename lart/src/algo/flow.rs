@@ -0,0 +1,375 @@
+use crate::{v, Bbox, Geometry, Grid, Noise, Path, QuadTree, Rect, PRECISION, TAU, V};
+
+/// A VectorField assigns a direction and a magnitude to each point of the
+/// plane.
+///
+/// Any closure from `V` to `V` is a VectorField.
+///
+/// ```rust
+/// # use lart::*;
+/// let swirl = |p: V| v(-p.y, p.x);
+/// assert_eq!(swirl.at(v(1, 0)), v(0, 1));
+/// ```
+pub trait VectorField {
+    fn at(&self, p: V) -> V;
+}
+
+impl<F: Fn(V) -> V> VectorField for F {
+    fn at(&self, p: V) -> V {
+        self(p)
+    }
+}
+
+/// A VectorField sampled on a Grid whose samples are evenly spread over a
+/// Rect, the vectors in between are bilinearly interpolated and the points
+/// outside of the Rect take the value of the closest sample.
+///
+/// ```rust
+/// # use lart::*;
+/// let mut grid = Grid::new(v(0, 0), 2, 2);
+/// grid[(1, 0)] = v(1, 0);
+/// grid[(1, 1)] = v(1, 0);
+///
+/// let field = GridField::new(grid, Rect::with_dimensions(v(0, 0), 10.0, 10.0));
+/// assert_eq!(field.at(v(5, 5)), v(0.5, 0));
+/// assert_eq!(field.at(v(20, 5)), v(1, 0));
+/// ```
+#[derive(Debug, Clone)]
+pub struct GridField {
+    pub grid: Grid<V>,
+    pub bounds: Rect,
+}
+
+/// A VectorField made of unit vectors whose angle is given by a Noise.
+#[derive(Debug, Clone)]
+pub struct NoiseField<N> {
+    pub noise: N,
+
+    /// The frequency the noise is sampled at.
+    pub frequency: f64,
+
+    /// The third coordinate the noise is sampled at, it can be changed over
+    /// time to animate the field.
+    pub z: f64,
+}
+
+/// A divergence free VectorField computed as the curl of a Noise used as
+/// potential, that is the field swirls around without sources nor sinks.
+///
+/// See [Curl-Noise for Procedural Fluid Flow][0].
+///
+/// [0]: https://www.cs.ubc.ca/~rbridson/docs/bridson-siggraph2007-curlnoise.pdf
+#[derive(Debug, Clone)]
+pub struct CurlNoise<N> {
+    pub noise: N,
+
+    /// The frequency the noise is sampled at.
+    pub frequency: f64,
+
+    /// The third coordinate the noise is sampled at, it can be changed over
+    /// time to animate the field.
+    pub z: f64,
+}
+
+impl GridField {
+    pub fn new(grid: Grid<V>, bounds: Rect) -> Self {
+        Self { grid, bounds }
+    }
+}
+
+impl VectorField for GridField {
+    fn at(&self, p: V) -> V {
+        let (w, h) = (self.grid.width(), self.grid.height());
+        if w == 0 || h == 0 {
+            return v(0, 0);
+        }
+
+        let cell = |size: usize, t: f64| {
+            let t = (t * (size - 1) as f64).clamp(0.0, (size - 1) as f64);
+            let i = (t.floor() as usize).min(size.saturating_sub(2));
+            (i, (i + 1).min(size - 1), t - i as f64)
+        };
+
+        let (x0, x1, tx) = cell(w, (p.x - self.bounds.left()) / self.bounds.width());
+        let (y0, y1, ty) = cell(h, (p.y - self.bounds.top()) / self.bounds.height());
+
+        let top = self.grid[(x0, y0)] * (1.0 - tx) + self.grid[(x1, y0)] * tx;
+        let bottom = self.grid[(x0, y1)] * (1.0 - tx) + self.grid[(x1, y1)] * tx;
+        top * (1.0 - ty) + bottom * ty
+    }
+}
+
+impl<N> NoiseField<N> {
+    pub fn new(noise: N, frequency: f64) -> Self {
+        Self {
+            noise,
+            frequency,
+            z: 0.0,
+        }
+    }
+}
+
+impl<N: Noise> VectorField for NoiseField<N> {
+    fn at(&self, p: V) -> V {
+        let p = p * self.frequency;
+        V::polar(self.noise.noise3(p.x, p.y, self.z) * TAU, 1.0)
+    }
+}
+
+impl<N> CurlNoise<N> {
+    pub fn new(noise: N, frequency: f64) -> Self {
+        Self {
+            noise,
+            frequency,
+            z: 0.0,
+        }
+    }
+}
+
+impl<N: Noise> VectorField for CurlNoise<N> {
+    fn at(&self, p: V) -> V {
+        const EPS: f64 = 1e-4;
+
+        let p = p * self.frequency;
+        let n = |x: f64, y: f64| self.noise.noise3(x, y, self.z);
+
+        let dx = (n(p.x + EPS, p.y) - n(p.x - EPS, p.y)) / (2.0 * EPS);
+        let dy = (n(p.x, p.y + EPS) - n(p.x, p.y - EPS)) / (2.0 * EPS);
+        v(dy, -dx)
+    }
+}
+
+/// Trace the streamline of the given VectorField that starts at the given
+/// point by integrating the direction of the field with the [Runge-Kutta][0]
+/// method of order 4.
+///
+/// The streamline is made of at most `max_steps` segments each `step` long,
+/// a negative step traces the streamline backwards. The tracing stops early
+/// where the field vanishes.
+///
+/// [0]: https://en.wikipedia.org/wiki/Runge%E2%80%93Kutta_methods
+///
+/// ```rust
+/// # use lart::*;
+/// let swirl = |p: V| v(-p.y, p.x);
+/// let p = streamline(&swirl, v(10, 0), 0.1, 100);
+/// assert_eq!(p.len(), 101);
+/// assert!(p.iter().all(|p| (p.norm() - 10.0).abs() < 1e-6));
+///
+/// assert_eq!(streamline(&|_| v(0, 0), v(10, 0), 0.1, 100).len(), 1);
+/// ```
+pub fn streamline(field: &impl VectorField, start: V, step: f64, max_steps: usize) -> Path {
+    let mut path = Path::from([start]);
+
+    let mut p = start;
+    for _ in 0..max_steps {
+        let Some(next) = rk4(field, p, step) else {
+            break;
+        };
+
+        path.push(next);
+        p = next;
+    }
+
+    path
+}
+
+/// Place evenly spaced streamlines of a VectorField inside a region following
+/// the algorithm by [Jobard and Lefer][0].
+///
+/// New streamlines are seeded `separation` away from the already placed ones
+/// and they're traced in both directions until they get closer than
+/// `test_separation` to another streamline, they leave the clip region or the
+/// field vanishes.
+///
+/// [0]: https://web.cs.ucdavis.edu/~ma/ECS177/papers/creating_evenly_spaced_streamlines.pdf
+#[derive(Debug, Clone)]
+pub struct StreamlinePlacer {
+    /// The region the streamlines are placed in.
+    pub clip: Geometry,
+
+    /// The distance between the seed of a new streamline and the existing
+    /// streamlines.
+    pub separation: f64,
+
+    /// The minimum distance between streamlines, usually a fraction of
+    /// `separation`.
+    pub test_separation: f64,
+
+    /// The length of each integration step.
+    pub step: f64,
+
+    /// The maximum number of steps of each streamline in each direction.
+    pub max_steps: usize,
+
+    /// Streamlines shorter than this are discarded.
+    pub min_length: f64,
+}
+
+impl StreamlinePlacer {
+    /// Create a new StreamlinePlacer that places streamlines `separation`
+    /// apart inside the given clip region.
+    pub fn new(clip: impl Into<Geometry>, separation: f64) -> Self {
+        Self {
+            clip: clip.into(),
+            separation,
+            test_separation: separation / 2.0,
+            step: separation / 4.0,
+            max_steps: 10_000,
+            min_length: 0.0,
+        }
+    }
+
+    /// Place the streamlines of the given VectorField starting from the
+    /// given seeds, or from the center of the clip region if there are none.
+    ///
+    /// The parts of the clip region that cannot be reached from the seeds
+    /// are then filled by seeding new streamlines on a grid.
+    ///
+    /// ```rust
+    /// # use lart::*;
+    /// let bbox = Rect::with_dimensions(v(0, 0), 102.0, 102.0);
+    /// let lines = StreamlinePlacer::new(bbox.clone(), 5.0).place(&|_| v(1, 0), []);
+    ///
+    /// // horizontal lines each 5 apart filling the square
+    /// assert_eq!(lines.len(), 21);
+    /// for l in &lines {
+    ///     assert!(l.norm() >= 99.0);
+    ///     assert!(l.iter().all(|p| (p.y - l[0].y).abs() < 1e-9));
+    /// }
+    /// ```
+    pub fn place(&self, field: &impl VectorField, seeds: impl IntoIterator<Item = V>) -> Vec<Path> {
+        let Some(bbox) = self.clip.bbox() else {
+            return vec![];
+        };
+
+        let mut index = QuadTree::new(bbox.clone(), vec![]);
+        let mut lines: Vec<Path> = vec![];
+
+        let mut seeds = seeds.into_iter().collect::<Vec<_>>();
+        if seeds.is_empty() {
+            seeds.push(bbox.center());
+        }
+        seeds.reverse();
+
+        // once no more streamlines can be seeded next to the existing ones
+        // fall back to a grid of seeds to reach the rest of the clip region
+        let mut fallback = bbox
+            .subdivide(
+                (bbox.width() / self.separation).ceil().max(1.0) as u32,
+                (bbox.height() / self.separation).ceil().max(1.0) as u32,
+            )
+            .map(|r| r.center())
+            .collect::<Vec<_>>();
+        fallback.reverse();
+
+        // the streamlines are processed in order and each one seeds its
+        // neighbors on both sides
+        let mut next_line = 0;
+        loop {
+            while let Some(seed) = seeds.pop() {
+                if !self.is_free(&index, seed, self.separation) {
+                    continue;
+                }
+
+                let line = self.trace(field, &index, seed);
+                if line.len() < 2 || line.norm() < self.min_length {
+                    continue;
+                }
+
+                for p in line.iter() {
+                    index.insert(p);
+                }
+                lines.push(line);
+            }
+
+            let Some(line) = lines.get(next_line) else {
+                match fallback.pop() {
+                    Some(seed) => seeds.push(seed),
+                    None => break,
+                }
+                continue;
+            };
+            next_line += 1;
+
+            for (a, b) in line.segments() {
+                let d = (b - a).normalized();
+                let n = v(-d.y, d.x) * self.separation;
+                seeds.extend([a - n, a + n]);
+            }
+            seeds.reverse();
+        }
+
+        lines
+    }
+
+    fn trace(&self, field: &impl VectorField, index: &QuadTree<V>, seed: V) -> Path {
+        let mut backward = self.trace_half(field, index, seed, -self.step);
+        let forward = self.trace_half(field, index, seed, self.step);
+
+        // a closed loop is traced entirely by the forward pass
+        if forward.is_closed() && forward.len() > 2 {
+            return forward;
+        }
+
+        backward.reverse();
+        backward.pop();
+        backward.extend(forward.iter());
+        backward
+    }
+
+    fn trace_half(
+        &self,
+        field: &impl VectorField,
+        index: &QuadTree<V>,
+        seed: V,
+        step: f64,
+    ) -> Path {
+        let mut path = Path::from([seed]);
+
+        let mut p = seed;
+        for i in 0..self.max_steps {
+            let Some(next) = rk4(field, p, step) else {
+                break;
+            };
+
+            // stop once the streamline loops back on itself
+            if i > 2 && next.dist(seed) < step.abs() {
+                path.push(seed);
+                break;
+            }
+
+            // the points of the streamline being traced are not in the index
+            // yet, so it's never considered too close to itself
+            if !self.is_free(index, next, self.test_separation) {
+                break;
+            }
+
+            path.push(next);
+            p = next;
+        }
+
+        path
+    }
+
+    /// Return whether the given point is in the clip region and at least
+    /// `dist` away from all the streamlines, up to `PRECISION`.
+    fn is_free(&self, index: &QuadTree<V>, p: V, dist: f64) -> bool {
+        self.clip.contains(p) && index.in_range(p, dist - PRECISION).is_empty()
+    }
+}
+
+fn rk4(field: &impl VectorField, p: V, h: f64) -> Option<V> {
+    let dir = |p: V| {
+        let d = field.at(p);
+        let n = d.norm();
+        (n > 1e-12).then(|| d / n)
+    };
+
+    let k1 = dir(p)?;
+    let k2 = dir(p + k1 * (h / 2.0))?;
+    let k3 = dir(p + k2 * (h / 2.0))?;
+    let k4 = dir(p + k3 * h)?;
+
+    Some(p + (k1 + k2 * 2.0 + k3 * 2.0 + k4) * (h / 6.0))
+}
@@ -0,0 +1,201 @@
+use crate::{v, Rect, Region, V};
+
+/// The number of samples of the clip region per site used to approximate the
+/// centroids of the Voronoi cells.
+const LLOYD_SAMPLES_PER_SITE: usize = 256;
+
+/// The minimum number of samples of the clip region, so that few sites still
+/// get accurate centroids.
+const LLOYD_MIN_SAMPLES: usize = 256 * 256;
+
+/// Spread the given points more evenly inside the clip region by running the
+/// given number of iterations of [Lloyd's algorithm][0], that is by moving
+/// each point to the centroid of its Voronoi cell.
+///
+/// The relaxed points are returned in the same order as the input ones.
+///
+/// See `weighted_lloyd_relax` for more details.
+///
+/// [0]: https://en.wikipedia.org/wiki/Lloyd%27s_algorithm
+///
+/// ```rust
+/// # use lart::*;
+/// let mut doc = Sketch::new("lloyd_relax").with_seed(42);
+/// let bbox = Rect::with_dimensions(v(0, 0), 100.0, 100.0);
+/// let pts = (0..100).map(|_| V::in_rect(&mut doc, &bbox)).collect::<Vec<_>>();
+///
+/// let min_dist = |pts: &[V]| {
+///     let mut d = f64::INFINITY;
+///     for (i, p) in pts.iter().enumerate() {
+///         for q in &pts[i + 1..] {
+///             d = d.min(p.dist(*q));
+///         }
+///     }
+///     d
+/// };
+///
+/// let relaxed = lloyd_relax(&pts, &bbox, 20);
+/// assert_eq!(relaxed.len(), pts.len());
+/// assert!(relaxed.iter().all(|p| bbox.contains(*p)));
+/// assert!(min_dist(&relaxed) > 5.0);
+/// assert!(min_dist(&relaxed) > 2.0 * min_dist(&pts));
+/// ```
+pub fn lloyd_relax(points: &[V], clip: &impl Region, iterations: usize) -> Vec<V> {
+    weighted_lloyd_relax(points, clip, iterations, |_| 1.0)
+}
+
+/// Relax the given points inside the clip region like `lloyd_relax`, but
+/// weighting the centroid of each Voronoi cell by the given density function
+/// so that the points concentrate where the density is higher, following
+/// [Weighted Voronoi Stippling][0] by Adrian Secord.
+///
+/// The density is expected to be non negative, the points where the density
+/// is 0 do not attract the points at all. The centroids are approximated by
+/// sampling the clip region on a regular grid, points whose Voronoi cell does
+/// not contain any sample stay where they are.
+///
+/// [0]: https://www.cs.ubc.ca/labs/imager/tr/2002/secord2002b/secord.2002b.pdf
+///
+/// ```rust
+/// # use lart::*;
+/// let mut doc = Sketch::new("weighted_lloyd_relax").with_seed(42);
+/// let bbox = Rect::with_dimensions(v(0, 0), 100.0, 100.0);
+/// let pts = (0..200).map(|_| V::in_rect(&mut doc, &bbox)).collect::<Vec<_>>();
+///
+/// // darker on the right, the points slowly move there
+/// let stipples = weighted_lloyd_relax(&pts, &bbox, 20, |p| (p.x / 100.0).powi(2));
+///
+/// let on_left = |pts: &[V]| pts.iter().filter(|p| p.x < 50.0).count();
+/// assert_eq!(stipples.len(), pts.len());
+/// assert!(on_left(&stipples) < on_left(&pts));
+/// assert!(stipples.iter().all(|p| bbox.contains(*p)));
+/// ```
+pub fn weighted_lloyd_relax(
+    points: &[V],
+    clip: &impl Region,
+    iterations: usize,
+    density: impl Fn(V) -> f64,
+) -> Vec<V> {
+    let mut sites = points.to_vec();
+    let Some(bbox) = clip.bbox() else {
+        return sites;
+    };
+    if sites.is_empty() || iterations == 0 {
+        return sites;
+    }
+
+    // the samples and their density never change across iterations, so
+    // compute them once
+    let nsamples = (sites.len() * LLOYD_SAMPLES_PER_SITE).max(LLOYD_MIN_SAMPLES);
+    let step = (bbox.area() / nsamples as f64).sqrt();
+    if step <= 0.0 {
+        return sites;
+    }
+
+    let cols = (bbox.width() / step).ceil().max(1.0) as usize;
+    let rows = (bbox.height() / step).ceil().max(1.0) as usize;
+    let samples = (0..rows)
+        .flat_map(|r| (0..cols).map(move |c| (c, r)))
+        .map(|(c, r)| bbox.min() + v(c as f64 + 0.5, r as f64 + 0.5) * step)
+        .filter(|&p| clip.contains(p))
+        .map(|p| (p, density(p).max(0.0)))
+        .filter(|&(_, w)| w > 0.0)
+        .collect::<Vec<_>>();
+
+    let mut centroids = vec![(v(0, 0), 0.0); sites.len()];
+    for _ in 0..iterations {
+        let index = SiteIndex::new(&bbox, &sites);
+
+        centroids.fill((v(0, 0), 0.0));
+        for &(p, w) in &samples {
+            let (c, tw) = &mut centroids[index.nearest(&sites, p)];
+            *c += p * w;
+            *tw += w;
+        }
+
+        for (site, &(c, w)) in sites.iter_mut().zip(&centroids) {
+            if w > 0.0 {
+                *site = c / w;
+            }
+        }
+    }
+
+    sites
+}
+
+/// A uniform grid of buckets of sites to quickly find the site closest to a
+/// given point.
+struct SiteIndex {
+    bbox: Rect,
+    cell: f64,
+    cols: usize,
+    rows: usize,
+    buckets: Vec<Vec<usize>>,
+}
+
+impl SiteIndex {
+    fn new(bbox: &Rect, sites: &[V]) -> Self {
+        // about one site per bucket
+        let cell = (bbox.area() / sites.len() as f64).sqrt().max(f64::EPSILON);
+        let cols = (bbox.width() / cell).ceil().max(1.0) as usize;
+        let rows = (bbox.height() / cell).ceil().max(1.0) as usize;
+
+        let mut index = Self {
+            bbox: bbox.clone(),
+            cell,
+            cols,
+            rows,
+            buckets: vec![vec![]; cols * rows],
+        };
+
+        for (i, s) in sites.iter().enumerate() {
+            let (c, r) = index.bucket(*s);
+            index.buckets[r * cols + c].push(i);
+        }
+
+        index
+    }
+
+    fn bucket(&self, p: V) -> (usize, usize) {
+        let c = ((p.x - self.bbox.left()) / self.cell).floor().max(0.0) as usize;
+        let r = ((p.y - self.bbox.top()) / self.cell).floor().max(0.0) as usize;
+        (c.min(self.cols - 1), r.min(self.rows - 1))
+    }
+
+    fn nearest(&self, sites: &[V], p: V) -> usize {
+        let (pc, pr) = self.bucket(p);
+        let (pc, pr) = (pc as isize, pr as isize);
+
+        let mut best = (usize::MAX, f64::INFINITY);
+        for ring in 0..self.cols.max(self.rows) as isize {
+            // all the buckets in the next rings are at least this far away
+            // from the given point
+            if best.0 != usize::MAX && best.1 <= (ring - 1).max(0) as f64 * self.cell {
+                break;
+            }
+
+            for r in pr - ring..=pr + ring {
+                for c in pc - ring..=pc + ring {
+                    let on_ring = (r - pr).abs() == ring || (c - pc).abs() == ring;
+                    if !on_ring
+                        || r < 0
+                        || c < 0
+                        || r >= self.rows as isize
+                        || c >= self.cols as isize
+                    {
+                        continue;
+                    }
+
+                    for &i in &self.buckets[r as usize * self.cols + c as usize] {
+                        let d = sites[i].dist(p);
+                        if d < best.1 {
+                            best = (i, d);
+                        }
+                    }
+                }
+            }
+        }
+
+        best.0
+    }
+}
@@ -1,11 +1,18 @@
 pub mod chaikin;
 pub mod circle_packing;
+pub mod contours;
 pub mod convex_hull;
+pub mod flow;
 pub mod kmeans;
+pub mod lloyd;
+pub mod noise;
+pub mod poisson;
 pub mod sample;
 pub mod simplify;
+pub mod skeleton;
 pub mod spline;
 pub mod split;
+pub mod triangulation;
 pub mod voro_tri;
 pub mod wfc;
 
@@ -13,11 +20,18 @@ use std::f64::consts::TAU;
 
 pub use chaikin::*;
 pub use circle_packing::*;
+pub use contours::*;
 pub use convex_hull::*;
+pub use flow::*;
 pub use kmeans::*;
+pub use lloyd::*;
+pub use noise::*;
+pub use poisson::*;
 pub use sample::*;
 pub use simplify::*;
+pub use skeleton::*;
 pub use split::*;
+pub use triangulation::*;
 pub use voro_tri::*;
 
 use crate::V;
@@ -0,0 +1,554 @@
+use rand::{seq::SliceRandom, Rng};
+
+/// A coherent noise function defined on the plane and in space.
+///
+/// All the generators are built from an `Rng` so that sketches stay
+/// reproducible given their seed. The 3D variants are handy to animate a 2D
+/// field by using time as the third dimension.
+///
+/// ```rust
+/// # use lart::*;
+/// # use rand::SeedableRng;
+/// let mut rng = MyRng::seed_from_u64(42);
+/// let noise = Fbm::new(Perlin::new(&mut rng));
+///
+/// let n = noise.noise2(1.5, 3.2);
+/// assert!((-1.0..=1.0).contains(&n));
+///
+/// // the same seed always produces the same noise
+/// let other = Fbm::new(Perlin::new(&mut MyRng::seed_from_u64(42)));
+/// assert_eq!(other.noise2(1.5, 3.2), n);
+/// ```
+pub trait Noise {
+    /// Sample the noise at the given point of the plane.
+    fn noise2(&self, x: f64, y: f64) -> f64;
+
+    /// Sample the noise at the given point in space.
+    fn noise3(&self, x: f64, y: f64, z: f64) -> f64;
+}
+
+/// Classic gradient [Perlin noise][0] in the improved version, roughly in
+/// `-1..=1`.
+///
+/// [0]: https://mrl.cs.nyu.edu/~perlin/noise/
+#[derive(Debug, Clone)]
+pub struct Perlin {
+    perm: Permutation,
+}
+
+/// Gradient noise on a simplex lattice in the style of [OpenSimplex2][0],
+/// roughly in `-1..=1`.
+///
+/// Compared to Perlin noise it shows less directional artifacts along the
+/// axes.
+///
+/// [0]: https://github.com/KdotJPG/OpenSimplex2
+#[derive(Debug, Clone)]
+pub struct OpenSimplex {
+    perm: Permutation,
+}
+
+/// [Worley noise][0], also known as cellular noise, that is the distance of
+/// the sampled point from the closest of a set of feature points randomly
+/// scattered one per unit cell.
+///
+/// The feature points can be anywhere in their cell, so the closest ones are
+/// searched in the 5x5 cells around the sampled point.
+///
+/// Unlike the other generators the output is a distance in `0..~1.5`.
+///
+/// [0]: https://en.wikipedia.org/wiki/Worley_noise
+#[derive(Debug, Clone)]
+pub struct Worley {
+    perm: Permutation,
+    jitter: Vec<[f64; 3]>,
+
+    /// Which distance to return.
+    pub feature: WorleyFeature,
+}
+
+/// The distances Worley noise can return.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum WorleyFeature {
+    /// The distance from the closest feature point.
+    F1,
+
+    /// The distance from the second closest feature point.
+    F2,
+
+    /// The difference between the distances from the second closest and the
+    /// closest feature points which draws the edges of the cells.
+    F2MinusF1,
+}
+
+/// Fractal Brownian motion, that is the sum of several octaves of the given
+/// noise each with increasing frequency and decreasing amplitude.
+///
+/// The result is normalized by the total amplitude so that it stays in the
+/// same range of the underlying noise.
+#[derive(Debug, Clone)]
+pub struct Fbm<N> {
+    pub noise: N,
+    pub octaves: u32,
+
+    /// How much the frequency increases at each octave.
+    pub lacunarity: f64,
+
+    /// How much the amplitude decreases at each octave.
+    pub gain: f64,
+}
+
+/// Ridged multifractal noise, like `Fbm` but each octave is folded around
+/// zero so that sharp ridges appear where the underlying noise crosses zero.
+///
+/// The result is in `-1..=1` if the underlying noise is.
+#[derive(Debug, Clone)]
+pub struct Ridged<N> {
+    pub noise: N,
+    pub octaves: u32,
+    pub lacunarity: f64,
+    pub gain: f64,
+}
+
+/// Turbulence, like `Fbm` but summing the absolute value of each octave.
+///
+/// The result is in `0..=1` if the underlying noise is in `-1..=1`.
+#[derive(Debug, Clone)]
+pub struct Turbulence<N> {
+    pub noise: N,
+    pub octaves: u32,
+    pub lacunarity: f64,
+    pub gain: f64,
+}
+
+/// [Domain warping][0], that is sampling a noise at points displaced by
+/// another noise.
+///
+/// [0]: https://iquilezles.org/articles/warp/
+#[derive(Debug, Clone)]
+pub struct DomainWarp<N, W> {
+    pub noise: N,
+    pub warp: W,
+
+    /// How far the sampled points are displaced.
+    pub strength: f64,
+}
+
+/// A random permutation of the first 256 integers used to hash lattice
+/// points.
+#[derive(Debug, Clone)]
+struct Permutation([u8; 256]);
+
+impl Permutation {
+    fn new(rng: &mut impl Rng) -> Self {
+        let mut p = [0; 256];
+        for (i, p) in p.iter_mut().enumerate() {
+            *p = i as u8;
+        }
+        p.shuffle(rng);
+        Self(p)
+    }
+
+    fn hash2(&self, x: i64, y: i64) -> usize {
+        let h = self.0[(x & 255) as usize] as i64;
+        self.0[((h + y) & 255) as usize] as usize
+    }
+
+    fn hash3(&self, x: i64, y: i64, z: i64) -> usize {
+        let h = self.hash2(x, y) as i64;
+        self.0[((h + z) & 255) as usize] as usize
+    }
+}
+
+impl Perlin {
+    pub fn new(rng: &mut impl Rng) -> Self {
+        Self {
+            perm: Permutation::new(rng),
+        }
+    }
+}
+
+impl Noise for Perlin {
+    fn noise2(&self, x: f64, y: f64) -> f64 {
+        let (x0, y0) = (x.floor(), y.floor());
+        let (fx, fy) = (x - x0, y - y0);
+        let (x0, y0) = (x0 as i64, y0 as i64);
+
+        let grad = |ix: i64, iy: i64, dx: f64, dy: f64| {
+            let g = PERLIN_GRADIENTS_3D[self.perm.hash2(ix, iy) & 15];
+            g[0] * dx + g[1] * dy
+        };
+
+        let (u, w) = (fade(fx), fade(fy));
+        lerp(
+            lerp(grad(x0, y0, fx, fy), grad(x0 + 1, y0, fx - 1.0, fy), u),
+            lerp(
+                grad(x0, y0 + 1, fx, fy - 1.0),
+                grad(x0 + 1, y0 + 1, fx - 1.0, fy - 1.0),
+                u,
+            ),
+            w,
+        )
+    }
+
+    fn noise3(&self, x: f64, y: f64, z: f64) -> f64 {
+        let (x0, y0, z0) = (x.floor(), y.floor(), z.floor());
+        let f = [x - x0, y - y0, z - z0];
+        let b = [x0 as i64, y0 as i64, z0 as i64];
+
+        let corner = |i: i64, j: i64, k: i64| {
+            let g = PERLIN_GRADIENTS_3D[self.perm.hash3(b[0] + i, b[1] + j, b[2] + k) & 15];
+            g[0] * (f[0] - i as f64) + g[1] * (f[1] - j as f64) + g[2] * (f[2] - k as f64)
+        };
+
+        let (u, w, t) = (fade(f[0]), fade(f[1]), fade(f[2]));
+        lerp(
+            lerp(
+                lerp(corner(0, 0, 0), corner(1, 0, 0), u),
+                lerp(corner(0, 1, 0), corner(1, 1, 0), u),
+                w,
+            ),
+            lerp(
+                lerp(corner(0, 0, 1), corner(1, 0, 1), u),
+                lerp(corner(0, 1, 1), corner(1, 1, 1), u),
+                w,
+            ),
+            t,
+        )
+    }
+}
+
+impl OpenSimplex {
+    pub fn new(rng: &mut impl Rng) -> Self {
+        Self {
+            perm: Permutation::new(rng),
+        }
+    }
+}
+
+impl Noise for OpenSimplex {
+    fn noise2(&self, x: f64, y: f64) -> f64 {
+        const SKEW: f64 = 0.366_025_403_784_438_6; // (sqrt(3) - 1) / 2
+        const UNSKEW: f64 = -0.211_324_865_405_187_1; // (1 / sqrt(3) - 1) / 2
+        const R2: f64 = 0.5;
+        const NORMALIZER: f64 = 99.2;
+
+        // find the triangle of the simplex lattice containing the point
+        let s = (x + y) * SKEW;
+        let (xs, ys) = (x + s, y + s);
+        let (xb, yb) = (xs.floor(), ys.floor());
+        let (xi, yi) = (xs - xb, ys - yb);
+        let (xb, yb) = (xb as i64, yb as i64);
+
+        let t = (xi + yi) * UNSKEW;
+        let (dx0, dy0) = (xi + t, yi + t);
+
+        let (ox, oy) = if xi > yi { (1, 0) } else { (0, 1) };
+        let vertices = [
+            (0, 0, dx0, dy0),
+            (ox, oy, dx0 - ox as f64 - UNSKEW, dy0 - oy as f64 - UNSKEW),
+            (1, 1, dx0 - 1.0 - 2.0 * UNSKEW, dy0 - 1.0 - 2.0 * UNSKEW),
+        ];
+
+        let mut value = 0.0;
+        for (i, j, dx, dy) in vertices {
+            let a = R2 - dx * dx - dy * dy;
+            if a > 0.0 {
+                let g = SIMPLEX_GRADIENTS_2D[self.perm.hash2(xb + i, yb + j) & 15];
+                value += a.powi(4) * (g[0] * dx + g[1] * dy);
+            }
+        }
+
+        value * NORMALIZER
+    }
+
+    fn noise3(&self, x: f64, y: f64, z: f64) -> f64 {
+        const R2: f64 = 0.6;
+        const NORMALIZER: f64 = 32.7;
+
+        // reflect the space so that the main diagonal of the lattice doesn't
+        // line up with the axes
+        let r = (x + y + z) * (2.0 / 3.0);
+        let p = [r - x, r - y, r - z];
+
+        // the body centered cubic lattice is made of two cubic lattices
+        // offset by half a cell, in each of them only the closest point and
+        // the next one along the axis of the largest offset can contribute
+        let mut value = 0.0;
+        for (lattice, offset) in [(0, 0.0), (1, 0.5)] {
+            let q = p.map(|c| c - offset);
+            let b = q.map(|c| c.round());
+            let d = [q[0] - b[0], q[1] - b[1], q[2] - b[2]];
+            let b = b.map(|c| c as i64 * 2 + lattice);
+
+            let k = (0..3)
+                .max_by(|&i, &j| d[i].abs().total_cmp(&d[j].abs()))
+                .unwrap();
+            let step = d[k].signum();
+
+            let mut b2 = b;
+            b2[k] += 2 * step as i64;
+            let mut d2 = d;
+            d2[k] -= step;
+
+            for (b, d) in [(b, d), (b2, d2)] {
+                let a = R2 - d[0] * d[0] - d[1] * d[1] - d[2] * d[2];
+                if a > 0.0 {
+                    let g = PERLIN_GRADIENTS_3D[self.perm.hash3(b[0], b[1], b[2]) & 15];
+                    value += a.powi(4) * (g[0] * d[0] + g[1] * d[1] + g[2] * d[2]);
+                }
+            }
+        }
+
+        value * NORMALIZER
+    }
+}
+
+impl Worley {
+    pub fn new(rng: &mut impl Rng) -> Self {
+        Self {
+            perm: Permutation::new(rng),
+            jitter: (0..256).map(|_| rng.gen()).collect(),
+            feature: WorleyFeature::F1,
+        }
+    }
+
+    /// Use the given feature for the output of the noise.
+    pub fn with_feature(mut self, feature: WorleyFeature) -> Self {
+        self.feature = feature;
+        self
+    }
+
+    /// Return the distances from the closest and the second closest feature
+    /// points from the given point of the plane.
+    pub fn distances2(&self, x: f64, y: f64) -> (f64, f64) {
+        let (cx, cy) = (x.floor() as i64, y.floor() as i64);
+
+        let mut d = (f64::INFINITY, f64::INFINITY);
+        for j in WORLEY_OFFSETS.map(|o| cy + o) {
+            for i in WORLEY_OFFSETS.map(|o| cx + o) {
+                if cell_dist2(x, i) + cell_dist2(y, j) >= d.1 {
+                    continue;
+                }
+
+                let o = self.jitter[self.perm.hash2(i, j)];
+                let (dx, dy) = (i as f64 + o[0] - x, j as f64 + o[1] - y);
+                d = push_distance(d, dx * dx + dy * dy);
+            }
+        }
+
+        (d.0.sqrt(), d.1.sqrt())
+    }
+
+    /// Return the distances from the closest and the second closest feature
+    /// points from the given point in space.
+    pub fn distances3(&self, x: f64, y: f64, z: f64) -> (f64, f64) {
+        let (cx, cy, cz) = (x.floor() as i64, y.floor() as i64, z.floor() as i64);
+
+        let mut d = (f64::INFINITY, f64::INFINITY);
+        for k in WORLEY_OFFSETS.map(|o| cz + o) {
+            for j in WORLEY_OFFSETS.map(|o| cy + o) {
+                for i in WORLEY_OFFSETS.map(|o| cx + o) {
+                    if cell_dist2(x, i) + cell_dist2(y, j) + cell_dist2(z, k) >= d.1 {
+                        continue;
+                    }
+
+                    let o = self.jitter[self.perm.hash3(i, j, k)];
+                    let (dx, dy, dz) = (
+                        i as f64 + o[0] - x,
+                        j as f64 + o[1] - y,
+                        k as f64 + o[2] - z,
+                    );
+                    d = push_distance(d, dx * dx + dy * dy + dz * dz);
+                }
+            }
+        }
+
+        (d.0.sqrt(), d.1.sqrt())
+    }
+
+    fn feature(&self, (f1, f2): (f64, f64)) -> f64 {
+        match self.feature {
+            WorleyFeature::F1 => f1,
+            WorleyFeature::F2 => f2,
+            WorleyFeature::F2MinusF1 => f2 - f1,
+        }
+    }
+}
+
+impl Noise for Worley {
+    fn noise2(&self, x: f64, y: f64) -> f64 {
+        self.feature(self.distances2(x, y))
+    }
+
+    fn noise3(&self, x: f64, y: f64, z: f64) -> f64 {
+        self.feature(self.distances3(x, y, z))
+    }
+}
+
+macro_rules! impl_fractal {
+    ($t: ident, $octave: expr, $finish: expr) => {
+        impl<N> $t<N> {
+            /// Create a new fractal noise with 6 octaves, each one with
+            /// double the frequency and half the amplitude of the previous
+            /// one.
+            pub fn new(noise: N) -> Self {
+                Self {
+                    noise,
+                    octaves: 6,
+                    lacunarity: 2.0,
+                    gain: 0.5,
+                }
+            }
+
+            pub fn with_octaves(mut self, octaves: u32) -> Self {
+                self.octaves = octaves;
+                self
+            }
+
+            pub fn with_lacunarity(mut self, lacunarity: f64) -> Self {
+                self.lacunarity = lacunarity;
+                self
+            }
+
+            pub fn with_gain(mut self, gain: f64) -> Self {
+                self.gain = gain;
+                self
+            }
+
+            fn octaves(&self, sample: impl Fn(f64) -> f64) -> f64 {
+                let (mut freq, mut amp) = (1.0, 1.0);
+                let (mut sum, mut total) = (0.0, 0.0);
+                for _ in 0..self.octaves {
+                    sum += amp * $octave(sample(freq));
+                    total += amp;
+                    freq *= self.lacunarity;
+                    amp *= self.gain;
+                }
+
+                if total == 0.0 {
+                    return 0.0;
+                }
+
+                $finish(sum / total)
+            }
+        }
+
+        impl<N: Noise> Noise for $t<N> {
+            fn noise2(&self, x: f64, y: f64) -> f64 {
+                self.octaves(|f| self.noise.noise2(x * f, y * f))
+            }
+
+            fn noise3(&self, x: f64, y: f64, z: f64) -> f64 {
+                self.octaves(|f| self.noise.noise3(x * f, y * f, z * f))
+            }
+        }
+    };
+}
+
+impl_fractal!(Fbm, |n: f64| n, |n: f64| n);
+impl_fractal!(Ridged, |n: f64| (1.0 - n.abs()).powi(2), |n: f64| n * 2.0
+    - 1.0);
+impl_fractal!(Turbulence, |n: f64| n.abs(), |n: f64| n);
+
+impl<N, W> DomainWarp<N, W> {
+    pub fn new(noise: N, warp: W, strength: f64) -> Self {
+        Self {
+            noise,
+            warp,
+            strength,
+        }
+    }
+}
+
+impl<N: Noise, W: Noise> Noise for DomainWarp<N, W> {
+    fn noise2(&self, x: f64, y: f64) -> f64 {
+        // sample the warp far apart to get uncorrelated displacements
+        let s = self.strength;
+        let dx = self.warp.noise2(x, y);
+        let dy = self.warp.noise2(x + 5.2, y + 1.3);
+        self.noise.noise2(x + dx * s, y + dy * s)
+    }
+
+    fn noise3(&self, x: f64, y: f64, z: f64) -> f64 {
+        let s = self.strength;
+        let dx = self.warp.noise3(x, y, z);
+        let dy = self.warp.noise3(x + 5.2, y + 1.3, z + 2.8);
+        let dz = self.warp.noise3(x + 1.7, y + 9.2, z + 4.1);
+        self.noise.noise3(x + dx * s, y + dy * s, z + dz * s)
+    }
+}
+
+fn fade(t: f64) -> f64 {
+    t * t * t * (t * (t * 6.0 - 15.0) + 10.0)
+}
+
+fn lerp(a: f64, b: f64, t: f64) -> f64 {
+    a + (b - a) * t
+}
+
+/// Return the squared distance of the given coordinate from the `i`-th unit
+/// cell along the same axis.
+fn cell_dist2(x: f64, i: i64) -> f64 {
+    let d = f64::max(i as f64 - x, x - (i + 1) as f64).max(0.0);
+    d * d
+}
+
+fn push_distance((d1, d2): (f64, f64), d: f64) -> (f64, f64) {
+    if d < d1 {
+        (d, d1)
+    } else if d < d2 {
+        (d1, d)
+    } else {
+        (d1, d2)
+    }
+}
+
+/// The offsets of the cells around the sampled one where the closest feature
+/// points of Worley noise can be, the closest ones first.
+const WORLEY_OFFSETS: [i64; 5] = [0, -1, 1, -2, 2];
+
+/// The directions towards the middle of the edges of a cube with four of them
+/// repeated to have a power of two number of gradients.
+const PERLIN_GRADIENTS_3D: [[f64; 3]; 16] = [
+    [1.0, 1.0, 0.0],
+    [-1.0, 1.0, 0.0],
+    [1.0, -1.0, 0.0],
+    [-1.0, -1.0, 0.0],
+    [1.0, 0.0, 1.0],
+    [-1.0, 0.0, 1.0],
+    [1.0, 0.0, -1.0],
+    [-1.0, 0.0, -1.0],
+    [0.0, 1.0, 1.0],
+    [0.0, -1.0, 1.0],
+    [0.0, 1.0, -1.0],
+    [0.0, -1.0, -1.0],
+    [1.0, 1.0, 0.0],
+    [-1.0, 1.0, 0.0],
+    [0.0, -1.0, 1.0],
+    [0.0, -1.0, -1.0],
+];
+
+const H: f64 = std::f64::consts::FRAC_1_SQRT_2;
+const C: f64 = 0.923_879_532_511_286_7; // cos(PI / 8)
+const S: f64 = 0.382_683_432_365_089_8; // sin(PI / 8)
+
+/// Sixteen unit vectors evenly spaced around the circle.
+const SIMPLEX_GRADIENTS_2D: [[f64; 2]; 16] = [
+    [1.0, 0.0],
+    [C, S],
+    [H, H],
+    [S, C],
+    [0.0, 1.0],
+    [-S, C],
+    [-H, H],
+    [-C, S],
+    [-1.0, 0.0],
+    [-C, -S],
+    [-H, -H],
+    [-S, -C],
+    [0.0, -1.0],
+    [S, -C],
+    [H, -H],
+    [C, -S],
+];
@@ -0,0 +1,204 @@
+use rand::{seq::SliceRandom, Rng};
+
+use crate::{v, Grid, Rect, Region, TAU, V};
+
+/// The number of candidates tried around each point before giving up on it.
+const POISSON_CANDIDATES: usize = 30;
+
+/// The number of random candidates tried in each empty cell of the grid when
+/// searching for a new seed, other than its center.
+const POISSON_SEED_CANDIDATES: usize = 8;
+
+/// Fill the given Region with random points that are at least `min_dist`
+/// apart from each other, but that are still tightly packed, following
+/// [Bridson's algorithm][0] for Poisson disk sampling.
+///
+/// Unlike uniformly random points, the points do not form clumps nor leave
+/// big empty areas.
+///
+/// New seeds are searched in all the cells of the background grid that are
+/// still empty, so regions that cover a small part of their bbox and regions
+/// made of several disjoint parts get filled too.
+///
+/// [0]: https://www.cs.ubc.ca/~rbridson/docs/bridson-siggraph07-poissondisk.pdf
+///
+/// ```rust
+/// # use lart::*;
+/// let mut doc = Sketch::new("poisson_disk").with_seed(42);
+/// let bbox = Rect::with_dimensions(v(0, 0), 100.0, 100.0);
+/// let pts = poisson_disk(&mut doc, &bbox, 5.0);
+///
+/// assert!(pts.len() > 250);
+/// assert!(pts.iter().all(|p| bbox.contains(*p)));
+/// for (i, p) in pts.iter().enumerate() {
+///     assert!(pts[i + 1..].iter().all(|q| p.dist(*q) >= 5.0));
+/// }
+///
+/// // a square with a hole in the middle
+/// let mut hole = Rect::with_dimensions(v(25, 25), 50.0, 50.0).closed_path();
+/// hole.reverse();
+/// let frame = Geometry::from_paths(vec![bbox.closed_path(), hole]);
+/// let pts = poisson_disk(&mut doc, &frame, 5.0);
+/// assert!(pts.len() > 150);
+/// assert!(pts.iter().all(|p| frame.contains(*p)));
+///
+/// // thin and disjoint regions get seeded as well
+/// let strips = Geometry::from_paths(vec![
+///     Rect::with_dimensions(v(0, 0), 100.0, 1.0).closed_path(),
+///     Rect::with_dimensions(v(0, 99), 100.0, 1.0).closed_path(),
+///     Rect::with_dimensions(v(50, 50), 2.0, 2.0).closed_path(),
+/// ]);
+/// let pts = poisson_disk(&mut doc, &strips, 5.0);
+/// assert!(pts.iter().all(|p| strips.contains(*p)));
+/// assert!(pts.iter().filter(|p| p.y < 1.0).count() >= 10);
+/// assert!(pts.iter().filter(|p| p.y > 99.0).count() >= 10);
+/// assert!(pts.iter().any(|p| p.y > 50.0 && p.y < 52.0));
+/// ```
+pub fn poisson_disk(rng: &mut impl Rng, region: &impl Region, min_dist: f64) -> Vec<V> {
+    poisson_disk_variable(rng, region, min_dist, min_dist, |_| 1.0)
+}
+
+/// Fill the given Region with random points like `poisson_disk`, but where
+/// the distance between the points changes according to the given density
+/// function.
+///
+/// The density is expected to be in `0..=1`, points where the density is 1
+/// are `min_dist` apart while points where it's 0 are `max_dist` apart.
+///
+/// ```rust
+/// # use lart::*;
+/// let mut doc = Sketch::new("poisson_disk_variable").with_seed(42);
+/// let bbox = Rect::with_dimensions(v(0, 0), 100.0, 100.0);
+///
+/// // denser on the right
+/// let pts = poisson_disk_variable(&mut doc, &bbox, 2.0, 10.0, |p| p.x / 100.0);
+///
+/// let left = pts.iter().filter(|p| p.x < 50.0).count();
+/// let right = pts.len() - left;
+/// assert!(right > 2 * left);
+/// assert!(pts.iter().all(|p| bbox.contains(*p)));
+/// ```
+pub fn poisson_disk_variable(
+    rng: &mut impl Rng,
+    region: &impl Region,
+    min_dist: f64,
+    max_dist: f64,
+    density: impl Fn(V) -> f64,
+) -> Vec<V> {
+    let Some(bbox) = region.bbox() else {
+        return vec![];
+    };
+
+    let (min_dist, max_dist) = (min_dist.min(max_dist), max_dist.max(min_dist));
+    if min_dist <= 0.0 {
+        return vec![];
+    }
+
+    let radius = |p: V| {
+        let d = density(p).clamp(0.0, 1.0);
+        max_dist + (min_dist - max_dist) * d
+    };
+
+    // each cell of the grid can contain at most a point because its diagonal
+    // is shorter than the minimum distance between any two points
+    let cell = min_dist / 2.0_f64.sqrt();
+    let reach = (max_dist / cell).ceil() as isize;
+    let mut grid = Grid::new(
+        None,
+        (bbox.width() / cell).floor() as usize + 1,
+        (bbox.height() / cell).floor() as usize + 1,
+    );
+    let cell_of = |p: V| {
+        (
+            ((p.x - bbox.left()) / cell).floor() as isize,
+            ((p.y - bbox.top()) / cell).floor() as isize,
+        )
+    };
+
+    let mut points: Vec<(V, f64)> = vec![];
+    let is_free = |grid: &Grid<Option<usize>>, points: &[(V, f64)], p: V, r: f64| {
+        let (cx, cy) = cell_of(p);
+        for y in cy - reach..=cy + reach {
+            for x in cx - reach..=cx + reach {
+                if x < 0 || y < 0 || x as usize >= grid.width() || y as usize >= grid.height() {
+                    continue;
+                }
+
+                let Some(i) = grid[(x as usize, y as usize)] else {
+                    continue;
+                };
+
+                // two points must be at least as far apart as the average of
+                // their radii
+                let (q, rq) = points[i];
+                if p.dist(q) < (r + rq) / 2.0 {
+                    return false;
+                }
+            }
+        }
+
+        // checking the distance from the other points is usually cheaper
+        region.contains(p)
+    };
+
+    // new seeds are searched in the cells of the grid in random order, a
+    // cell is never searched again because it can only get more crowded
+    let mut seed_cells = grid.indices().collect::<Vec<_>>();
+    seed_cells.shuffle(rng);
+
+    let mut active = vec![];
+    loop {
+        if active.is_empty() {
+            // seed the next empty cell that still has room for a point, this
+            // also reaches the parts of the region that are disjoint from
+            // the already filled ones
+            let mut seed = None;
+            while let Some((x, y)) = seed_cells.pop() {
+                if grid[(x, y)].is_some() {
+                    continue;
+                }
+
+                let cell_rect =
+                    Rect::with_dimensions(bbox.min() + v(x as f64, y as f64) * cell, cell, cell);
+                seed = std::iter::once(cell_rect.center())
+                    .chain((0..POISSON_SEED_CANDIDATES).map(|_| V::in_rect(rng, &cell_rect)))
+                    .find(|&p| bbox.contains(p) && is_free(&grid, &points, p, radius(p)));
+
+                if seed.is_some() {
+                    break;
+                }
+            }
+
+            let Some(seed) = seed else {
+                break;
+            };
+
+            let (x, y) = cell_of(seed);
+            grid[(x as usize, y as usize)] = Some(points.len());
+            active.push(points.len());
+            points.push((seed, radius(seed)));
+        }
+
+        let ai = rng.gen_range(0..active.len());
+        let (p, r) = points[active[ai]];
+
+        let candidate = (0..POISSON_CANDIDATES)
+            .map(|_| p + V::polar(rng.gen_range(0.0..TAU), rng.gen_range(r..=2.0 * r)))
+            .map(|c| (c, radius(c)))
+            .find(|&(c, rc)| bbox.contains(c) && is_free(&grid, &points, c, rc));
+
+        match candidate {
+            Some((c, rc)) => {
+                let (x, y) = cell_of(c);
+                grid[(x as usize, y as usize)] = Some(points.len());
+                active.push(points.len());
+                points.push((c, rc));
+            }
+            None => {
+                active.swap_remove(ai);
+            }
+        }
+    }
+
+    points.into_iter().map(|(p, _)| p).collect()
+}
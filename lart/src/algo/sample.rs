@@ -1,4 +1,6 @@
-use crate::{frange, V};
+use rand::Rng;
+
+use crate::{frange, triangulate_polygon, ArcLengthIndex, Geometry, Path, Triangulation, V};
 
 #[derive(Debug, Clone, PartialEq)]
 pub struct PathSample {
@@ -53,3 +55,155 @@ pub fn sample_seg(a: V, b: V, step: f64, include_b: bool) -> impl Iterator<Item
             None
         })
 }
+
+impl Triangulation {
+    /// Return a random point inside the Triangulation, uniformly distributed
+    /// over its area.
+    ///
+    /// Return None if the Triangulation has no area.
+    ///
+    /// ```rust
+    /// # use lart::*;
+    /// let mut doc = Sketch::new("triangulation_random_point").with_seed(42);
+    /// let square = Rect::with_dimensions(v(0, 0), 10.0, 10.0).closed_path();
+    /// let tri = triangulate_polygons(&[Polygon::new(square.clone(), vec![])]);
+    ///
+    /// let p = tri.random_point(&mut doc).unwrap();
+    /// assert!(square.contains(p));
+    /// assert_eq!(Triangulation::default().random_point(&mut doc), None);
+    /// ```
+    pub fn random_point(&self, rng: &mut impl Rng) -> Option<V> {
+        self.random_points(rng, 1).pop()
+    }
+
+    /// Return `n` random points inside the Triangulation, uniformly
+    /// distributed over its area.
+    ///
+    /// Return no points if the Triangulation has no area.
+    ///
+    /// ```rust
+    /// # use lart::*;
+    /// let mut doc = Sketch::new("triangulation_random_points").with_seed(42);
+    /// let tri = triangulate_polygons(&[
+    ///     Polygon::new(Rect::with_dimensions(v(0, 0), 10.0, 10.0).closed_path(), vec![]),
+    ///     Polygon::new(Rect::with_dimensions(v(20, 0), 10.0, 10.0).closed_path(), vec![]),
+    /// ]);
+    ///
+    /// // the two squares have the same area and so they get roughly the
+    /// // same number of points
+    /// let pts = tri.random_points(&mut doc, 1000);
+    /// assert_eq!(pts.len(), 1000);
+    /// let left = pts.iter().filter(|p| p.x <= 10.0).count();
+    /// assert!((400..600).contains(&left));
+    /// ```
+    pub fn random_points(&self, rng: &mut impl Rng, n: usize) -> Vec<V> {
+        // cumulative areas of the triangles to pick them proportionally to
+        // their area
+        let mut areas = Vec::with_capacity(self.len());
+        let mut total = 0.0;
+        for i in 0..self.len() {
+            let [a, b, c] = self.triangle(i);
+            total += c.orient(a, b).abs() / 2.0;
+            areas.push(total);
+        }
+
+        if total <= 0.0 {
+            return vec![];
+        }
+
+        (0..n)
+            .map(|_| {
+                let s = rng.gen_range(0.0..total);
+                let i = areas.partition_point(|&a| a <= s).min(self.len() - 1);
+                let [a, b, c] = self.triangle(i);
+
+                // fold the points falling in the other half of the
+                // parallelogram back into the triangle
+                let (mut u, mut w) = (rng.gen_range(0.0..=1.0), rng.gen_range(0.0..=1.0));
+                if u + w > 1.0 {
+                    (u, w) = (1.0 - u, 1.0 - w);
+                }
+
+                a + (b - a) * u + (c - a) * w
+            })
+            .collect()
+    }
+}
+
+impl Geometry {
+    /// Return a random point inside the area delimited by the closed paths
+    /// of this Geometry, uniformly distributed over its area and honoring its
+    /// holes.
+    ///
+    /// Return None if the Geometry does not enclose any area.
+    ///
+    /// ```rust
+    /// # use lart::*;
+    /// let mut doc = Sketch::new("geometry_random_point").with_seed(42);
+    /// let g = Geometry::from(Rect::with_dimensions(v(0, 0), 10.0, 10.0))
+    ///     - Rect::with_dimensions(v(3, 3), 4.0, 4.0);
+    ///
+    /// let p = g.random_point(&mut doc).unwrap();
+    /// assert!(g.contains(p));
+    /// assert_eq!(Geometry::from(path!(v(0, 0), v(1, 1))).random_point(&mut doc), None);
+    /// ```
+    pub fn random_point(&self, rng: &mut impl Rng) -> Option<V> {
+        triangulate_polygon(self).random_point(rng)
+    }
+
+    /// Return `n` random points inside the area delimited by the closed paths
+    /// of this Geometry, uniformly distributed over its area and honoring its
+    /// holes.
+    ///
+    /// The Geometry is triangulated only once, so this is much faster than
+    /// calling `Geometry::random_point` repeatedly.
+    pub fn random_points(&self, rng: &mut impl Rng, n: usize) -> Vec<V> {
+        triangulate_polygon(self).random_points(rng, n)
+    }
+}
+
+impl Path {
+    /// Return a random point on the Path, uniformly distributed over its
+    /// length.
+    ///
+    /// Return None if the Path is empty.
+    ///
+    /// ```rust
+    /// # use lart::*;
+    /// let mut doc = Sketch::new("path_random_point_on").with_seed(42);
+    /// let p = path!(v(0, 0), v(10, 0), v(10, 10));
+    ///
+    /// let q = p.random_point_on(&mut doc).unwrap();
+    /// assert!(p.distance(q) < 1e-9);
+    /// assert_eq!(Path::new().random_point_on(&mut doc), None);
+    /// ```
+    pub fn random_point_on(&self, rng: &mut impl Rng) -> Option<V> {
+        self.random_points_on(rng, 1).pop()
+    }
+
+    /// Return `n` random points on the Path, uniformly distributed over its
+    /// length.
+    ///
+    /// ```rust
+    /// # use lart::*;
+    /// let mut doc = Sketch::new("path_random_points_on").with_seed(42);
+    /// let p = path!(v(0, 0), v(10, 0), v(10, 30));
+    ///
+    /// // the vertical segment is three times longer
+    /// let pts = p.random_points_on(&mut doc, 1000);
+    /// assert_eq!(pts.len(), 1000);
+    /// let horizontal = pts.iter().filter(|q| q.y == 0.0).count();
+    /// assert!((200..300).contains(&horizontal));
+    /// ```
+    pub fn random_points_on(&self, rng: &mut impl Rng, n: usize) -> Vec<V> {
+        if self.is_empty() {
+            return vec![];
+        }
+
+        let ix = ArcLengthIndex::new(self);
+        let len = ix.len();
+        (0..n)
+            .map(|_| ix.point_at(rng.gen_range(0.0..=len)))
+            .collect()
+    }
+}
@@ -0,0 +1,485 @@
+use crate::{circumcenter, path, v, Bbox, Geometry, Path, PointLocation, Polygon, V};
+
+/// A Skeleton is a set of segments lying in the middle of a shape, the
+/// distance from the boundary of the shape is stored for each vertex.
+#[derive(Debug, Clone)]
+pub struct Skeleton {
+    /// The edges of the skeleton, each one as a two points Path.
+    pub geometry: Geometry,
+
+    /// The distances from the boundary of the shape of the two endpoints of
+    /// each edge.
+    pub distances: Vec<[f64; 2]>,
+}
+
+impl Skeleton {
+    fn new() -> Self {
+        Self {
+            geometry: Geometry::new(),
+            distances: vec![],
+        }
+    }
+
+    fn push_edge(&mut self, (a, da): (V, f64), (b, db): (V, f64)) {
+        if a.almost_equal(b) {
+            return;
+        }
+
+        self.geometry.push_path(path!(a, b));
+        self.distances.push([da, db]);
+    }
+}
+
+/// Compute the [straight skeleton][0] of the given Polygon, that is the trace
+/// of the vertices of the polygon while its edges move inwards at the same
+/// speed.
+///
+/// The distance of each vertex of the skeleton is the time at which it's
+/// reached by the wavefront, which is also its distance from the supporting
+/// lines of the edges that generated it. It makes it perfect to give height to
+/// shapes like the roof of a house.
+///
+/// The wavefront is simulated one event at a time, which takes cubic time in
+/// the number of vertices. Rounding errors might keep the wavefront of
+/// degenerate polygons from collapsing, in that case `None` is returned.
+///
+/// [0]: https://en.wikipedia.org/wiki/Straight_skeleton
+///
+/// ```rust
+/// # use lart::*;
+/// let rect = Polygon::new(Rect::with_dimensions(v(0, 0), 10.0, 4.0).closed_path(), vec![]);
+/// let sk = straight_skeleton(&rect).unwrap();
+/// assert_eq!(sk.geometry.paths().len(), 5);
+///
+/// let (ridge, d) = sk
+///     .geometry
+///     .paths()
+///     .iter()
+///     .zip(&sk.distances)
+///     .find(|(_, d)| d[0] > 0.0 && d[1] > 0.0)
+///     .unwrap();
+/// assert!((d[0] - 2.0).abs() < 1e-9 && (d[1] - 2.0).abs() < 1e-9);
+/// assert!((ridge.norm() - 6.0).abs() < 1e-9);
+/// ```
+pub fn straight_skeleton(poly: &Polygon) -> Option<Skeleton> {
+    let mut wf = Wavefront::new(poly);
+    wf.run().then_some(wf.skeleton)
+}
+
+/// Approximate the [medial axis][0] of the area covered by the closed paths of
+/// the given Geometry, that is the set of points that have more than one
+/// closest point on the boundary.
+///
+/// The boundary is sampled every `tolerance` and the medial axis is extracted
+/// from the Voronoi diagram of the samples. The branches that are due only to
+/// the sampling are pruned, but this also means that the branches going into
+/// the corners stop a few samples before reaching them.
+///
+/// The distance of each vertex of the skeleton is its distance from the
+/// closest sample on the boundary.
+///
+/// [0]: https://en.wikipedia.org/wiki/Medial_axis
+///
+/// ```rust
+/// # use lart::*;
+/// let rect = Geometry::from(Rect::with_dimensions(v(0, 0), 20.0, 4.0));
+/// let axis = medial_axis(&rect, 0.25);
+///
+/// assert!(!axis.geometry.is_empty());
+/// for (p, d) in axis.geometry.paths().iter().zip(&axis.distances) {
+///     assert!(p.iter().all(|pt| rect.contains(pt)));
+///     assert!(d.iter().all(|&d| d <= 2.0 + 0.25));
+/// }
+///
+/// // the center line runs through the whole length of the rectangle
+/// let bbox = axis.geometry.bbox().unwrap();
+/// assert!(bbox.width() > 14.0);
+/// ```
+pub fn medial_axis(g: &Geometry, tolerance: f64) -> Skeleton {
+    // samples of the boundary together with the ring they belong to and their
+    // index along such ring
+    let mut samples: Vec<(V, usize, usize)> = vec![];
+    let mut ring_lens = vec![];
+    for p in g.paths().iter().filter(|p| p.len() > 2 && p.is_closed()) {
+        let mut ring = p.sampled(tolerance);
+        ring.dedup();
+        if ring.first() == ring.last() {
+            ring.pop();
+        }
+
+        let r = ring_lens.len();
+        ring_lens.push(ring.len());
+        samples.extend(ring.iter().enumerate().map(|(i, pt)| (pt, r, i)));
+    }
+
+    let mut sk = Skeleton::new();
+
+    let pts = samples
+        .iter()
+        .map(|(p, _, _)| delaunator::Point { x: p.x, y: p.y })
+        .collect::<Vec<_>>();
+    let tri = delaunator::triangulate(&pts);
+
+    let circles = tri
+        .triangles
+        .chunks_exact(3)
+        .map(|t| {
+            // slivers of almost collinear samples lie on the boundary and
+            // their circumcenters are meaningless
+            let [a, b, c] = [t[0], t[1], t[2]].map(|i| samples[i].0);
+            if g.classify((a + b + c) / 3.0, tolerance * 1e-3) != PointLocation::Inside {
+                return None;
+            }
+            let cc = circumcenter(a, b, c)?;
+            g.contains(cc).then_some((cc, cc.dist(a)))
+        })
+        .collect::<Vec<_>>();
+
+    for (e, &twin) in tri.halfedges.iter().enumerate() {
+        // visit each pair of triangles only once
+        if twin == delaunator::EMPTY || twin < e {
+            continue;
+        }
+
+        let (Some(c0), Some(c1)) = (circles[e / 3], circles[twin / 3]) else {
+            continue;
+        };
+
+        // samples that are close along the boundary generate the spurious
+        // branches going towards the boundary itself
+        let (_, r0, i0) = samples[tri.triangles[e]];
+        let (_, r1, i1) = samples[tri.triangles[delaunator::next_halfedge(e)]];
+        if r0 == r1 {
+            let d = i0.abs_diff(i1);
+            if d.min(ring_lens[r0] - d) <= 2 {
+                continue;
+            }
+        }
+
+        sk.push_edge(c0, c1);
+    }
+
+    sk
+}
+
+#[derive(Debug, Clone, Copy)]
+struct Edge {
+    p: V,
+    dir: V,
+    normal: V,
+}
+
+/// A vertex of the wavefront that moves with constant velocity starting from
+/// `pos` at time `t0` along the bisector of its two edges.
+#[derive(Debug, Clone, Copy)]
+struct Vertex {
+    pos: V,
+    t0: f64,
+    vel: V,
+    in_edge: usize,
+    out_edge: usize,
+    prev: usize,
+    next: usize,
+    active: bool,
+    reflex: bool,
+}
+
+impl Vertex {
+    fn at(&self, t: f64) -> V {
+        self.pos + self.vel * (t - self.t0)
+    }
+}
+
+#[derive(Debug, Clone, Copy)]
+enum Event {
+    /// The edge going from the vertex to its next one shrinks to a point.
+    Edge { u: usize, t: f64 },
+
+    /// The reflex vertex `v` hits the edge going from `u` to its next vertex.
+    Split { v: usize, u: usize, t: f64 },
+}
+
+struct Wavefront {
+    edges: Vec<Edge>,
+    verts: Vec<Vertex>,
+    skeleton: Skeleton,
+    now: f64,
+    eps: f64,
+}
+
+impl Wavefront {
+    fn new(poly: &Polygon) -> Self {
+        let mut wf = Self {
+            edges: vec![],
+            verts: vec![],
+            skeleton: Skeleton::new(),
+            now: 0.0,
+            eps: 1e-9 * poly.bbox().map_or(1.0, |r| f64::max(r.width(), r.height())),
+        };
+
+        // the exterior winds counter-clockwise and the holes clockwise so that
+        // the inside is always on the left of the edges
+        wf.add_ring(&poly.exterior, true);
+        for h in &poly.holes {
+            wf.add_ring(h, false);
+        }
+
+        wf
+    }
+
+    fn add_ring(&mut self, path: &Path, ccw: bool) {
+        let mut pts = path.points().to_vec();
+        pts.dedup();
+        while pts.len() > 1 && pts.first() == pts.last() {
+            pts.pop();
+        }
+
+        // drop collinear vertices, they don't generate any skeleton edge
+        let mut i = 0;
+        while pts.len() >= 3 && i < pts.len() {
+            let n = pts.len();
+            let (a, b, c) = (pts[(i + n - 1) % n], pts[i], pts[(i + 1) % n]);
+            if c.orient(a, b) == 0.0 && (b - a).dot(c - b) > 0.0 {
+                pts.remove(i);
+            } else {
+                i += 1;
+            }
+        }
+
+        if pts.len() < 3 {
+            return;
+        }
+
+        let mut ring = Path::from(pts);
+        if (ring.signed_area() > 0.0) != ccw {
+            ring.reverse();
+        }
+
+        let n = ring.len();
+        let (base_e, base_v) = (self.edges.len(), self.verts.len());
+        for i in 0..n {
+            let dir = (ring[(i + 1) % n] - ring[i]).normalized();
+            self.edges.push(Edge {
+                p: ring[i],
+                dir,
+                normal: v(-dir.y, dir.x),
+            });
+        }
+
+        for (i, p) in ring.iter().enumerate() {
+            let vert = self.new_vertex(
+                p,
+                base_e + (i + n - 1) % n,
+                base_e + i,
+                (base_v + (i + n - 1) % n, base_v + (i + 1) % n),
+            );
+            self.verts.push(vert);
+        }
+    }
+
+    fn new_vertex(
+        &self,
+        pos: V,
+        in_edge: usize,
+        out_edge: usize,
+        (prev, next): (usize, usize),
+    ) -> Vertex {
+        let (e1, e2) = (&self.edges[in_edge], &self.edges[out_edge]);
+        let (n1, n2) = (e1.normal, e2.normal);
+
+        // the vertex must move at unit speed away from both edges
+        let det = n1.x * n2.y - n1.y * n2.x;
+        let vel = if det.abs() > 1e-12 {
+            v(n2.y - n1.y, n1.x - n2.x) / det
+        } else if n1.dot(n2) > 0.0 {
+            n1
+        } else {
+            v(0, 0)
+        };
+
+        Vertex {
+            pos,
+            t0: self.now,
+            vel,
+            in_edge,
+            out_edge,
+            prev,
+            next,
+            active: true,
+            reflex: e2.dir.orient(v(0, 0), e1.dir) < 0.0,
+        }
+    }
+
+    /// Process all the events and return whether the wavefront collapsed.
+    fn run(&mut self) -> bool {
+        // each edge event removes a vertex and each split event consumes a
+        // reflex vertex to add one, so there are at most n + 2r events in
+        // general position, leave some room for the degenerate cases
+        let max_events = 4 * self.verts.len() + 16;
+        for _ in 0..max_events {
+            let Some(event) = self.next_event() else {
+                return true;
+            };
+
+            match event {
+                Event::Edge { u, t } => self.edge_event(u, t),
+                Event::Split { v, u, t } => self.split_event(v, u, t),
+            }
+        }
+
+        self.next_event().is_none()
+    }
+
+    fn next_event(&self) -> Option<Event> {
+        let mut best: Option<(f64, Event)> = None;
+        let mut consider = |t: f64, e: Event| {
+            if t >= self.now - self.eps && !matches!(best, Some((bt, _)) if bt <= t) {
+                best = Some((t, e));
+            }
+        };
+
+        for (u, vu) in self.verts.iter().enumerate().filter(|(_, v)| v.active) {
+            if let Some(t) = self.edge_event_time(u) {
+                consider(t, Event::Edge { u, t });
+            }
+
+            if !vu.reflex {
+                continue;
+            }
+
+            for (o, vo) in self.verts.iter().enumerate().filter(|(_, v)| v.active) {
+                if o == u || vo.next == u {
+                    continue;
+                }
+
+                if let Some(t) = self.split_event_time(u, o) {
+                    consider(t, Event::Split { v: u, u: o, t });
+                }
+            }
+        }
+
+        best.map(|(_, e)| e)
+    }
+
+    fn edge_event_time(&self, u: usize) -> Option<f64> {
+        let vu = &self.verts[u];
+        let vw = &self.verts[vu.next];
+        if vu.next == u {
+            return None;
+        }
+
+        // simultaneous events can leave vertices that already met but that
+        // would move apart from now on
+        if vu.at(self.now).almost_equal(vw.at(self.now)) {
+            return Some(self.now);
+        }
+
+        let d = self.edges[vu.out_edge].dir;
+        let (a, b) = (vu.pos.dot(d) - vu.vel.dot(d) * vu.t0, vu.vel.dot(d));
+        let (c, dd) = (vw.pos.dot(d) - vw.vel.dot(d) * vw.t0, vw.vel.dot(d));
+        if b - dd <= 1e-12 {
+            return None;
+        }
+
+        Some(f64::max(self.now, (c - a) / (b - dd)))
+    }
+
+    fn split_event_time(&self, v: usize, u: usize) -> Option<f64> {
+        let vv = &self.verts[v];
+        let (vu, vw) = (&self.verts[u], &self.verts[self.verts[u].next]);
+        let e = vu.out_edge;
+        if e == vv.in_edge || e == vv.out_edge {
+            return None;
+        }
+
+        let edge = &self.edges[e];
+        let k = vv.vel.dot(edge.normal);
+        if 1.0 - k <= 1e-12 {
+            return None;
+        }
+
+        let d0 = (vv.pos - edge.p).dot(edge.normal);
+        let t = (d0 - k * vv.t0) / (1.0 - k);
+        if t < self.now - self.eps {
+            return None;
+        }
+
+        // the hit point must fall inside the shrunk edge
+        let x = vv.at(t);
+        let (a, b) = (vu.at(t), vw.at(t));
+        let s = (x - a).dot(edge.dir);
+        let l = (b - a).dot(edge.dir);
+        (s >= -self.eps && s <= l + self.eps).then_some(t)
+    }
+
+    fn edge_event(&mut self, u: usize, t: f64) {
+        self.now = t;
+
+        let vu = self.verts[u];
+        let w = vu.next;
+        let vw = self.verts[w];
+        let x = (vu.at(t) + vw.at(t)) / 2.0;
+
+        self.skeleton.push_edge((vu.pos, vu.t0), (x, t));
+        self.skeleton.push_edge((vw.pos, vw.t0), (x, t));
+        self.verts[u].active = false;
+        self.verts[w].active = false;
+
+        let (prev, next) = (vu.prev, vw.next);
+        if prev == w {
+            // the loop was only made of these two vertices
+            return;
+        }
+
+        let n = self.verts.len();
+        let vert = self.new_vertex(x, vu.in_edge, vw.out_edge, (prev, next));
+        self.verts.push(vert);
+        self.verts[prev].next = n;
+        self.verts[next].prev = n;
+
+        self.collapse(n);
+    }
+
+    fn split_event(&mut self, v: usize, u: usize, t: f64) {
+        self.now = t;
+
+        let vv = self.verts[v];
+        let w = self.verts[u].next;
+        let e = self.verts[u].out_edge;
+        let x = vv.at(t);
+
+        self.skeleton.push_edge((vv.pos, vv.t0), (x, t));
+        self.verts[v].active = false;
+
+        let n = self.verts.len();
+        let v1 = self.new_vertex(x, vv.in_edge, e, (vv.prev, w));
+        let v2 = self.new_vertex(x, e, vv.out_edge, (u, vv.next));
+        self.verts.push(v1);
+        self.verts.push(v2);
+
+        self.verts[vv.prev].next = n;
+        self.verts[w].prev = n;
+        self.verts[u].next = n + 1;
+        self.verts[vv.next].prev = n + 1;
+
+        self.collapse(n);
+        self.collapse(n + 1);
+    }
+
+    /// Remove the loop of the given vertex if it degenerated into a segment.
+    fn collapse(&mut self, i: usize) {
+        let vi = self.verts[i];
+        if !vi.active {
+            return;
+        }
+
+        if vi.next == i {
+            self.verts[i].active = false;
+        } else if self.verts[vi.next].next == i {
+            let vn = self.verts[vi.next];
+            self.skeleton.push_edge((vn.pos, vn.t0), (vi.pos, vi.t0));
+            self.verts[i].active = false;
+            self.verts[vi.next].active = false;
+        }
+    }
+}
@@ -0,0 +1,576 @@
+use std::collections::{HashMap, HashSet};
+
+use crate::{circumcenter, path, polygon, seg_x_seg, v, Geometry, Path, Polygon, V};
+
+/// A Triangulation is a set of triangles sharing their vertices.
+///
+/// All the triangles wind counter-clockwise in a coordinate system where y
+/// points up and `adjacency[t][i]` is the index of the triangle on the other
+/// side of the edge going from `triangles[t][i]` to `triangles[t][(i + 1) %
+/// 3]`, if any.
+#[derive(Debug, Clone, Default, PartialEq)]
+pub struct Triangulation {
+    pub points: Vec<V>,
+    pub triangles: Vec<[usize; 3]>,
+    pub adjacency: Vec<[Option<usize>; 3]>,
+}
+
+impl Triangulation {
+    /// Return the number of triangles.
+    pub fn len(&self) -> usize {
+        self.triangles.len()
+    }
+
+    /// Return whether there are no triangles at all.
+    pub fn is_empty(&self) -> bool {
+        self.triangles.is_empty()
+    }
+
+    /// Return the vertices of the i-th triangle.
+    pub fn triangle(&self, i: usize) -> [V; 3] {
+        self.triangles[i].map(|j| self.points[j])
+    }
+
+    /// Return the total area covered by the triangles.
+    pub fn area(&self) -> f64 {
+        (0..self.len())
+            .map(|i| {
+                let [a, b, c] = self.triangle(i);
+                c.orient(a, b).abs() / 2.0
+            })
+            .sum()
+    }
+
+    /// Return the center of the circle passing through the vertices of the
+    /// i-th triangle, if it's not degenerate.
+    pub fn circumcenter(&self, i: usize) -> Option<V> {
+        let [a, b, c] = self.triangle(i);
+        circumcenter(a, b, c)
+    }
+
+    /// Return the edges of the triangles as pairs of indices into `points`,
+    /// each edge shared by two triangles is returned only once.
+    ///
+    /// ```rust
+    /// # use lart::*;
+    /// let tri = triangulate(&[v(0, 0), v(10, 0), v(10, 10), v(0, 10)]);
+    /// assert_eq!(tri.len(), 2);
+    /// assert_eq!(tri.edges().count(), 5);
+    /// assert_eq!(tri.edges_geometry().paths().len(), 5);
+    /// ```
+    pub fn edges(&self) -> impl Iterator<Item = (usize, usize)> + '_ {
+        self.triangles
+            .iter()
+            .zip(&self.adjacency)
+            .enumerate()
+            .flat_map(|(t, (tri, adj))| {
+                (0..3)
+                    .filter(move |&k| !matches!(adj[k], Some(n) if n < t))
+                    .map(move |k| (tri[k], tri[(k + 1) % 3]))
+            })
+    }
+
+    /// Return the edges of the triangles as a Geometry made of segments
+    /// where each edge appears only once, so that it can be plotted without
+    /// drawing the shared edges twice.
+    pub fn edges_geometry(&self) -> Geometry {
+        Geometry::from_paths(
+            self.edges()
+                .map(|(a, b)| path!(self.points[a], self.points[b]))
+                .collect(),
+        )
+    }
+
+    /// Return the indices of the points connected to each point by an edge.
+    ///
+    /// ```rust
+    /// # use lart::*;
+    /// let tri = triangulate(&[v(0, 0), v(10, 0), v(10, 10), v(0, 10), v(5, 5)]);
+    /// let neighbors = tri.vertex_neighbors();
+    /// assert_eq!(neighbors[4], vec![0, 1, 2, 3]);
+    /// assert_eq!(neighbors[0], vec![1, 3, 4]);
+    /// ```
+    pub fn vertex_neighbors(&self) -> Vec<Vec<usize>> {
+        let mut neighbors = vec![vec![]; self.points.len()];
+        for (a, b) in self.edges() {
+            neighbors[a].push(b);
+            neighbors[b].push(a);
+        }
+
+        for n in &mut neighbors {
+            n.sort_unstable();
+            n.dedup();
+        }
+
+        neighbors
+    }
+
+    pub(crate) fn compute_adjacency(&mut self) {
+        let mut edges = HashMap::with_capacity(self.triangles.len() * 3);
+        for (i, t) in self.triangles.iter().enumerate() {
+            for k in 0..3 {
+                edges.insert((t[k], t[(k + 1) % 3]), i);
+            }
+        }
+
+        self.adjacency = self
+            .triangles
+            .iter()
+            .map(|t| std::array::from_fn(|k| edges.get(&(t[(k + 1) % 3], t[k])).copied()))
+            .collect();
+    }
+}
+
+impl From<&Triangulation> for Geometry {
+    fn from(t: &Triangulation) -> Self {
+        Geometry::from_paths(
+            (0..t.len())
+                .map(|i| {
+                    let [a, b, c] = t.triangle(i);
+                    polygon!(a, b, c)
+                })
+                .collect(),
+        )
+    }
+}
+
+impl From<Triangulation> for Geometry {
+    fn from(t: Triangulation) -> Self {
+        Geometry::from(&t)
+    }
+}
+
+/// Triangulate the area covered by the closed paths of the given Geometry
+/// respecting its boundaries and holes.
+///
+/// See `triangulate_polygons`.
+///
+/// ```rust
+/// # use lart::*;
+/// let outer = Rect::with_dimensions(v(0, 0), 10.0, 10.0);
+/// let hole = Rect::with_dimensions(v(3, 3), 4.0, 4.0);
+///
+/// let tri = triangulate_polygon(&(outer - hole));
+/// assert!((tri.area() - 84.0).abs() < 1e-6);
+/// ```
+pub fn triangulate_polygon(g: &Geometry) -> Triangulation {
+    triangulate_polygons(&g.polygons())
+}
+
+/// Triangulate the given Polygons respecting their boundaries and holes.
+///
+/// The holes are first bridged to the exterior so that the polygons can be
+/// triangulated by [ear clipping][0], then the triangles are improved by
+/// flipping edges until the triangulation is a [constrained Delaunay
+/// triangulation][1] where the edges of the polygons are the constraints.
+///
+/// Ear clipping takes quadratic time which is fine for the polygons usually
+/// found in sketches, but huge polygons are better split beforehand. Self
+/// intersecting polygons get overlapping triangles.
+///
+/// [0]: https://en.wikipedia.org/wiki/Polygon_triangulation#Ear_clipping_method
+/// [1]: https://en.wikipedia.org/wiki/Constrained_Delaunay_triangulation
+///
+/// ```rust
+/// # use lart::*;
+/// let outer = Rect::with_dimensions(v(0, 0), 10.0, 10.0).closed_path();
+/// let hole = Rect::with_dimensions(v(3, 3), 4.0, 4.0).closed_path();
+/// let tri = triangulate_polygons(&[Polygon::new(outer, vec![hole.clone()])]);
+///
+/// assert_eq!(tri.len(), 8);
+/// assert!((tri.area() - 84.0).abs() < 1e-9);
+/// for i in 0..tri.len() {
+///     let [a, b, c] = tri.triangle(i);
+///     assert!(!hole.contains((a + b + c) / 3.0));
+/// }
+///
+/// // the only edges without a neighbor are the ones on the boundaries
+/// let boundary = tri.adjacency.iter().flatten().filter(|n| n.is_none()).count();
+/// assert_eq!(boundary, 8);
+///
+/// let u = polygon!(v(0, 0), v(10, 0), v(10, 10), v(7, 10), v(7, 3), v(3, 3), v(3, 10), v(0, 10));
+/// let tri = triangulate_polygons(&[Polygon::new(u.clone(), vec![])]);
+/// assert_eq!(tri.len(), 6);
+/// for i in 0..tri.len() {
+///     let [a, b, c] = tri.triangle(i);
+///     assert!(u.contains((a + b + c) / 3.0));
+/// }
+///
+/// // the tip of the hole is aligned with a vertex and an edge of the step
+/// let step = polygon!(v(0, 0), v(10, 0), v(10, 5), v(6, 5), v(6, 10), v(0, 10));
+/// let hole = polygon!(v(2, 4), v(2, 6), v(4, 5));
+/// let tri = triangulate_polygons(&[Polygon::new(step, vec![hole.clone()])]);
+/// assert_eq!(tri.len(), 9);
+/// assert!((tri.area() - 78.0).abs() < 1e-9);
+/// for i in 0..tri.len() {
+///     let [a, b, c] = tri.triangle(i);
+///     assert!(!hole.contains((a + b + c) / 3.0));
+/// }
+/// ```
+pub fn triangulate_polygons(polygons: &[Polygon]) -> Triangulation {
+    let mut tri = Triangulation::default();
+    let mut constraints = HashSet::new();
+
+    for poly in polygons {
+        let Some(mut ring) = push_ring(&mut tri.points, &mut constraints, &poly.exterior, true)
+        else {
+            continue;
+        };
+
+        let mut holes = poly
+            .holes
+            .iter()
+            .filter_map(|h| push_ring(&mut tri.points, &mut constraints, h, false))
+            .collect::<Vec<_>>();
+
+        // bridge the holes from right to left so that each bridge never
+        // crosses a hole that's still to be bridged
+        let rightmost = |h: &Vec<usize>| {
+            h.iter()
+                .map(|&i| tri.points[i].x)
+                .fold(f64::NEG_INFINITY, f64::max)
+        };
+        holes.sort_by(|h0, h1| rightmost(h1).total_cmp(&rightmost(h0)));
+
+        for hole in holes {
+            bridge_hole(&tri.points, &mut ring, &hole);
+        }
+
+        ear_clip(&tri.points, &ring, &mut tri.triangles);
+    }
+
+    delaunay_flips(&tri.points, &mut tri.triangles, &constraints);
+    tri.compute_adjacency();
+
+    tri
+}
+
+/// Add the vertices of the given ring to the points returning their indices
+/// ordered counter-clockwise if `ccw` is true or clockwise otherwise.
+///
+/// Degenerate rings with less than three vertices are skipped.
+fn push_ring(
+    points: &mut Vec<V>,
+    constraints: &mut HashSet<(usize, usize)>,
+    path: &Path,
+    ccw: bool,
+) -> Option<Vec<usize>> {
+    let mut pts = path.points().to_vec();
+    pts.dedup();
+    while pts.len() > 1 && pts.first() == pts.last() {
+        pts.pop();
+    }
+    if pts.len() < 3 {
+        return None;
+    }
+
+    let mut pts = Path::from(pts);
+    if (pts.signed_area() > 0.0) != ccw {
+        pts.reverse();
+    }
+
+    let ring = (points.len()..points.len() + pts.len()).collect::<Vec<_>>();
+    points.extend(pts.iter());
+
+    for (i, &a) in ring.iter().enumerate() {
+        let b = ring[(i + 1) % ring.len()];
+        constraints.insert((a.min(b), a.max(b)));
+    }
+
+    Some(ring)
+}
+
+/// Merge the given clockwise hole into the counter-clockwise ring by adding a
+/// pair of coincident edges going from the rightmost vertex of the hole to a
+/// vertex of the ring that's visible from it.
+///
+/// See https://www.geometrictools.com/Documentation/TriangulationByEarClipping.pdf
+fn bridge_hole(points: &[V], ring: &mut Vec<usize>, hole: &[usize]) {
+    let (hi, m) = hole
+        .iter()
+        .enumerate()
+        .map(|(i, &j)| (i, points[j]))
+        .max_by(|(_, a), (_, b)| a.x.total_cmp(&b.x).then(b.y.total_cmp(&a.y)))
+        .unwrap();
+
+    // cast a ray towards +x and find the closest edge of the ring that faces
+    // the hole from the inside
+    let n = ring.len();
+    let mut closest: Option<(f64, usize)> = None;
+    for i in 0..n {
+        let (a, b) = (points[ring[i]], points[ring[(i + 1) % n]]);
+        if a.y > m.y || b.y < m.y || a.y == b.y {
+            continue;
+        }
+
+        let x = a.x + (m.y - a.y) * (b.x - a.x) / (b.y - a.y);
+        if x >= m.x && !matches!(closest, Some((cx, _)) if cx <= x) {
+            closest = Some((x, i));
+        }
+    }
+
+    let mut pos = match closest {
+        Some((x, i)) => ray_vertex(points, ring, m, (v(x, m.y), i)),
+        // the ray can miss the ring only when the hole is not strictly
+        // inside it, e.g. because they touch
+        None => nearest_visible_vertex(points, ring, hole, m),
+    };
+
+    // a vertex can appear more than once in the ring after other holes have
+    // been bridged, pick the occurrence whose corner contains the hole
+    let target = ring[pos];
+    if let Some(j) = (0..n).find(|&j| {
+        ring[j] == target
+            && in_cone(
+                points[ring[(j + n - 1) % n]],
+                points[ring[j]],
+                points[ring[(j + 1) % n]],
+                m,
+            )
+    }) {
+        pos = j;
+    }
+
+    let mut merged = Vec::with_capacity(n + hole.len() + 2);
+    merged.extend_from_slice(&ring[..=pos]);
+    merged.extend(hole[hi..].iter().chain(&hole[..=hi]));
+    merged.push(ring[pos]);
+    merged.extend_from_slice(&ring[pos + 1..]);
+    *ring = merged;
+}
+
+/// Return the position in the ring of the vertex visible from `m` through
+/// the point `ix` where the ray towards +x hits the edge starting at the
+/// `i`-th vertex.
+fn ray_vertex(points: &[V], ring: &[usize], m: V, (ix, i): (V, usize)) -> usize {
+    let (a, b) = (i, (i + 1) % ring.len());
+    let pos = if points[ring[a]].x > points[ring[b]].x {
+        a
+    } else {
+        b
+    };
+
+    // the endpoint of the edge might be hidden by other vertices of the ring
+    // falling inside the triangle M, I, P, in that case the vertex forming the
+    // smallest angle with the ray is visible
+    let p = points[ring[pos]];
+    if p == ix {
+        return pos;
+    }
+
+    let (t1, t2) = if p.orient(m, ix) >= 0.0 {
+        (ix, p)
+    } else {
+        (p, ix)
+    };
+
+    let mut best = None;
+    for (j, &r) in ring.iter().enumerate() {
+        let q = points[r];
+        if r == ring[pos] || !in_triangle(q, m, t1, t2) {
+            continue;
+        }
+
+        let d = q - m;
+        let key = (d.y.abs().atan2(d.x), d.norm2());
+        if !matches!(best, Some((k, _)) if k <= key) {
+            best = Some((key, j));
+        }
+    }
+
+    best.map_or(pos, |(_, j)| j)
+}
+
+/// Return the position in the ring of the closest vertex to `m` such that
+/// the segment between them crosses neither the ring nor the hole, or
+/// just the closest vertex if none is visible.
+fn nearest_visible_vertex(points: &[V], ring: &[usize], hole: &[usize], m: V) -> usize {
+    let mut candidates = (0..ring.len()).collect::<Vec<_>>();
+    candidates.sort_by(|&i, &j| {
+        m.dist2(points[ring[i]])
+            .total_cmp(&m.dist2(points[ring[j]]))
+    });
+
+    let edges = |r: &[usize]| {
+        (0..r.len())
+            .map(|i| (points[r[i]], points[r[(i + 1) % r.len()]]))
+            .collect::<Vec<_>>()
+    };
+    let edges = [edges(ring), edges(hole)].concat();
+
+    candidates
+        .iter()
+        .copied()
+        .find(|&i| {
+            let q = points[ring[i]];
+            edges.iter().all(|&(a, b)| {
+                [a, b].iter().any(|&e| e == m || e == q) || seg_x_seg((m, q), (a, b)).is_none()
+            })
+        })
+        .unwrap_or(candidates[0])
+}
+
+/// Triangulate the counter-clockwise ring by repeatedly cutting its ears.
+///
+/// The ring must be simple, apart from the coincident edges of the bridges.
+/// Otherwise, e.g. because of rounding errors, there might be no ear to cut
+/// and the vertices are cut anyway, so that the triangles still cover the
+/// ring even if some of them might overlap or fall outside of it.
+fn ear_clip(points: &[V], ring: &[usize], triangles: &mut Vec<[usize; 3]>) {
+    let n = ring.len();
+    if n < 3 {
+        return;
+    }
+
+    let mut prev = (0..n).map(|i| (i + n - 1) % n).collect::<Vec<_>>();
+    let mut next = (0..n).map(|i| (i + 1) % n).collect::<Vec<_>>();
+
+    let mut push_triangle = |t: [usize; 3]| {
+        let [a, b, c] = t.map(|i| points[i]);
+        if c.orient(a, b) != 0.0 {
+            triangles.push(t);
+        }
+    };
+
+    let mut remaining = n;
+    let mut i = 0;
+    let mut stalled = 0;
+    while remaining > 3 {
+        let (p, nx) = (prev[i], next[i]);
+        let t = [ring[p], ring[i], ring[nx]];
+
+        // if no ear can be found the ring is degenerate, cut the current
+        // vertex anyway to guarantee progress
+        if stalled < remaining && !is_ear(points, ring, &next, (p, nx), t) {
+            i = nx;
+            stalled += 1;
+            continue;
+        }
+
+        push_triangle(t);
+        next[p] = nx;
+        prev[nx] = p;
+        remaining -= 1;
+        stalled = 0;
+        i = p;
+    }
+
+    push_triangle([ring[prev[i]], ring[i], ring[next[i]]]);
+}
+
+fn is_ear(
+    points: &[V],
+    ring: &[usize],
+    next: &[usize],
+    (p, nx): (usize, usize),
+    t: [usize; 3],
+) -> bool {
+    let [a, b, c] = t.map(|i| points[i]);
+    if c.orient(a, b) <= 0.0 {
+        return false;
+    }
+
+    // check all the other vertices, skipping the copies of the ear vertices
+    // introduced by the bridges
+    let mut j = next[nx];
+    while j != p {
+        if !t.contains(&ring[j]) && in_triangle(points[ring[j]], a, b, c) {
+            return false;
+        }
+        j = next[j];
+    }
+
+    true
+}
+
+/// Flip the edges that are not constraints until all the triangles satisfy
+/// the Delaunay condition.
+fn delaunay_flips(
+    points: &[V],
+    triangles: &mut [[usize; 3]],
+    constraints: &HashSet<(usize, usize)>,
+) {
+    let mut edges = HashMap::with_capacity(triangles.len() * 3);
+    for (i, t) in triangles.iter().enumerate() {
+        for k in 0..3 {
+            edges.insert((t[k], t[(k + 1) % 3]), i);
+        }
+    }
+
+    // the iteration order of the map is random, seed the stack from the
+    // triangles so that the flips are always the same
+    let mut stack = triangles
+        .iter()
+        .flat_map(|t| (0..3).map(move |k| (t[k], t[(k + 1) % 3])))
+        .collect::<Vec<_>>();
+    while let Some((a, b)) = stack.pop() {
+        if constraints.contains(&(a.min(b), a.max(b))) {
+            continue;
+        }
+
+        let (Some(&t1), Some(&t2)) = (edges.get(&(a, b)), edges.get(&(b, a))) else {
+            continue;
+        };
+
+        let c = third_vertex(triangles[t1], a);
+        let d = third_vertex(triangles[t2], b);
+        let (pa, pb, pc, pd) = (points[a], points[b], points[c], points[d]);
+
+        // the quad must be convex for the flip to be valid
+        if !in_circle(pa, pb, pc, pd) || pa.orient(pc, pd) * pb.orient(pc, pd) >= 0.0 {
+            continue;
+        }
+
+        for t in [t1, t2] {
+            let t = triangles[t];
+            for k in 0..3 {
+                edges.remove(&(t[k], t[(k + 1) % 3]));
+            }
+        }
+
+        triangles[t1] = [a, d, c];
+        triangles[t2] = [d, b, c];
+        for t in [t1, t2] {
+            let tt = triangles[t];
+            for k in 0..3 {
+                edges.insert((tt[k], tt[(k + 1) % 3]), t);
+            }
+        }
+
+        stack.extend([(a, d), (d, b), (b, c), (c, a)]);
+    }
+}
+
+/// Return the vertex of the triangle that comes two places after `a`.
+fn third_vertex(t: [usize; 3], a: usize) -> usize {
+    let k = t.iter().position(|&v| v == a).unwrap();
+    t[(k + 2) % 3]
+}
+
+/// Return whether `d` is strictly inside the circle passing through the
+/// counter-clockwise triangle `a`, `b`, `c`.
+fn in_circle(a: V, b: V, c: V, d: V) -> bool {
+    let (a, b, c) = (a - d, b - d, c - d);
+    let (a2, b2, c2) = (a.norm2(), b.norm2(), c.norm2());
+    let det =
+        a2 * (b.x * c.y - c.x * b.y) - b2 * (a.x * c.y - c.x * a.y) + c2 * (a.x * b.y - b.x * a.y);
+
+    // cocircular points must not be flipped back and forth forever
+    det > 1e-10 * (a2 + b2 + c2).powi(2)
+}
+
+/// Return whether `p` is inside or on the boundary of the counter-clockwise
+/// triangle `a`, `b`, `c`.
+fn in_triangle(p: V, a: V, b: V, c: V) -> bool {
+    p.orient(a, b) >= 0.0 && p.orient(b, c) >= 0.0 && p.orient(c, a) >= 0.0
+}
+
+/// Return whether `p` is inside the corner at `b` of a counter-clockwise ring
+/// coming from `a` and going to `c`.
+fn in_cone(a: V, b: V, c: V, p: V) -> bool {
+    if c.orient(a, b) >= 0.0 {
+        p.orient(a, b) > 0.0 && p.orient(b, c) > 0.0
+    } else {
+        p.orient(a, b) > 0.0 || p.orient(b, c) > 0.0
+    }
+}
@@ -1,29 +1,174 @@
-use crate::{polygon, Geometry, Path, Rect, V};
+use std::collections::HashMap;
 
-/// Find a [triangulation][0] of the given set of points.
+use crate::{
+    bbox, bbox_union, linterp, path, polygon, seg_x_seg, seg_x_seg_t, v, Bbox, Geometry, Grid,
+    Path, Rect, Triangulation, PRECISION, V,
+};
+
+/// Find the [Delaunay triangulation][0] of the given set of points.
+///
+/// The points of the Triangulation are the given ones in the same order,
+/// duplicated points are not part of any triangle. Collinear points do not
+/// have any triangulation at all.
+///
+/// [0]: https://en.wikipedia.org/wiki/Delaunay_triangulation
+///
+/// ```rust
+/// # use lart::*;
+/// let pts = [v(0, 0), v(10, 0), v(10, 10), v(0, 10), v(5, 5)];
+/// let tri = triangulate(&pts);
+/// assert_eq!(tri.points, pts);
+/// assert_eq!(tri.len(), 4);
+/// assert_eq!(tri.area(), 100.0);
+/// assert!((0..tri.len()).all(|i| tri.circumcenter(i).is_some()));
 ///
-/// [0]: https://en.wikipedia.org/wiki/Triangulation
-pub fn triangulate(pts: &[V]) -> Geometry {
-    let pts = pts
-        .as_ref()
+/// assert!(triangulate(&[v(0, 0), v(1, 1), v(2, 2)]).is_empty());
+/// ```
+pub fn triangulate(pts: &[V]) -> Triangulation {
+    let dpts = pts
         .iter()
         .map(|vv| delaunator::Point { x: vv.x, y: vv.y })
         .collect::<Vec<_>>();
-    let tri = delaunator::triangulate(&pts);
-
-    let polys = tri
-        .triangles
-        .chunks_exact(3)
-        .map(|vs| {
-            polygon!(
-                (pts[vs[0]].x, pts[vs[0]].y),
-                (pts[vs[1]].x, pts[vs[1]].y),
-                (pts[vs[2]].x, pts[vs[2]].y),
-            )
-        })
-        .collect();
+    let dtri = delaunator::triangulate(&dpts);
+
+    let mut tri = Triangulation {
+        points: pts.to_vec(),
+        triangles: dtri
+            .triangles
+            .chunks_exact(3)
+            .map(|t| {
+                let (a, b, c) = (t[0], t[1], t[2]);
+                if pts[c].orient(pts[a], pts[b]) < 0.0 {
+                    [a, c, b]
+                } else {
+                    [a, b, c]
+                }
+            })
+            .collect(),
+        adjacency: vec![],
+    };
+    tri.compute_adjacency();
+
+    tri
+}
+
+/// A VoronoiDiagram partitions the plane in cells, one for each site, such
+/// that each cell contains the points that are closer to its site than to any
+/// other site.
+///
+/// `cells[i]` and `neighbors[i]` are the cell and the indices of the
+/// neighboring cells of `sites[i]` respectively.
+#[derive(Debug, Clone, Default)]
+pub struct VoronoiDiagram {
+    pub sites: Vec<V>,
+    pub cells: Vec<Geometry>,
+    pub neighbors: Vec<Vec<usize>>,
+
+    /// The unique edges of the cells together with the sites on their sides,
+    /// if the edge is not on the border of the clip region.
+    edges: Vec<Edge>,
+}
+
+#[derive(Debug, Clone, Copy)]
+struct Edge {
+    seg: (V, V),
+    sites: Option<(usize, usize)>,
+}
 
-    Geometry::from_paths(polys)
+impl VoronoiDiagram {
+    /// Return the number of cells of the diagram.
+    pub fn len(&self) -> usize {
+        self.cells.len()
+    }
+
+    /// Return whether the diagram has no cells at all.
+    pub fn is_empty(&self) -> bool {
+        self.cells.is_empty()
+    }
+
+    /// Return the cell of the i-th site.
+    pub fn cell(&self, i: usize) -> &Geometry {
+        &self.cells[i]
+    }
+
+    /// Return the edges of the cells, each edge shared by two cells is
+    /// returned only once.
+    pub fn edges(&self) -> impl Iterator<Item = (V, V)> + '_ {
+        self.edges.iter().map(|e| e.seg)
+    }
+
+    /// Return the edges shared by two cells, together with the indices of
+    /// the sites of such cells, that is all the edges except the ones on the
+    /// border of the clip region.
+    pub fn inner_edges(&self) -> impl Iterator<Item = (V, V, usize, usize)> + '_ {
+        self.edges
+            .iter()
+            .filter_map(|e| e.sites.map(|(i, j)| (e.seg.0, e.seg.1, i, j)))
+    }
+
+    /// Return the edges of the cells as a Geometry made of segments where
+    /// each edge appears only once, so that it can be plotted without
+    /// drawing the shared edges twice.
+    pub fn edges_geometry(&self) -> Geometry {
+        Geometry::from_paths(self.edges().map(|(a, b)| path!(a, b)).collect())
+    }
+}
+
+impl VoronoiDiagram {
+    /// Build a VoronoiDiagram from the convex cells of the sites whose
+    /// vertices are tagged with the site on the other side of the edge
+    /// starting from them, if any.
+    fn from_cells(sites: Vec<V>, cells: Vec<Vec<(V, Option<usize>)>>) -> Self {
+        let mut vor = VoronoiDiagram {
+            sites,
+            ..Default::default()
+        };
+
+        for (i, cell) in cells.into_iter().enumerate() {
+            let mut cell_neighbors = cell.iter().filter_map(|(_, j)| *j).collect::<Vec<_>>();
+            cell_neighbors.sort_unstable();
+            cell_neighbors.dedup();
+            vor.neighbors.push(cell_neighbors);
+
+            for (k, &(a, j)) in cell.iter().enumerate() {
+                // shared edges are kept by the cell of the site with the
+                // lowest index only
+                if j.is_some_and(|j| j < i) {
+                    continue;
+                }
+                vor.edges.push(Edge {
+                    seg: (a, cell[(k + 1) % cell.len()].0),
+                    sites: j.map(|j| (i, j)),
+                });
+            }
+
+            if cell.is_empty() {
+                vor.cells.push(Geometry::new());
+            } else {
+                vor.cells.push(Geometry::from(
+                    Path::from_iter(cell.iter().map(|(p, _)| *p)).closed(),
+                ));
+            }
+        }
+
+        vor
+    }
+}
+
+impl From<&VoronoiDiagram> for Geometry {
+    fn from(vor: &VoronoiDiagram) -> Self {
+        let mut g = Geometry::new();
+        for c in &vor.cells {
+            g.append(c);
+        }
+        g
+    }
+}
+
+impl From<VoronoiDiagram> for Geometry {
+    fn from(vor: VoronoiDiagram) -> Self {
+        Geometry::from(&vor)
+    }
 }
 
 /// Generate the [Voronoi diagram][0] for the given set of points clipping it to
@@ -32,28 +177,577 @@ pub fn triangulate(pts: &[V]) -> Geometry {
 /// The clipping rect is necessary because the Voronoi diagram extends to
 /// infinity geometrically speaking, but that's likely not what we want.
 ///
+/// The cells are computed by clipping the rect with the bisectors between
+/// each site and its neighbors in the Delaunay triangulation, so all the
+/// cells are convex. Duplicated sites get an empty cell except for the first
+/// one, sites outside of the clipping rect get an empty cell only if their
+/// cell does not reach the rect at all.
+///
 /// [0]: https://en.wikipedia.org/wiki/Voronoi_diagram
-pub fn voronoi(pts: &[V], clip: &Rect) -> Geometry {
-    let pts = pts
-        .as_ref()
+///
+/// ```rust
+/// # use lart::*;
+/// let bbox = Rect::with_dimensions(v(0, 0), 20.0, 10.0);
+/// let vor = voronoi(&[v(5, 5), v(15, 5), v(15, 5)], &bbox);
+///
+/// assert_eq!(vor.len(), 3);
+/// assert_eq!(vor.cell(0).area(), 100.0);
+/// assert!(vor.cell(0).contains(v(2, 2)));
+/// assert!(vor.cell(1).contains(v(18, 2)));
+/// assert!(vor.cell(2).paths().is_empty());
+///
+/// let outside = voronoi(&[v(5, 5), v(25, 5), v(50, 5)], &bbox);
+/// assert_eq!(outside.cell(1).area(), 50.0);
+/// assert!(outside.cell(2).paths().is_empty());
+///
+/// assert_eq!(vor.neighbors[0], vec![1]);
+/// assert_eq!(vor.neighbors[1], vec![0]);
+///
+/// // the two cells share the middle edge
+/// assert_eq!(vor.edges().count(), 7);
+/// assert_eq!(vor.inner_edges().count(), 1);
+///
+/// let pts = [v(2, 2), v(8, 3), v(5, 8), v(15, 2), v(12, 7), v(18, 8)];
+/// let vor = voronoi(&pts, &bbox);
+/// assert!((Geometry::from(&vor).area() - bbox.area()).abs() < 1e-9);
+/// for (i, p) in pts.iter().enumerate() {
+///     assert!(vor.cell(i).contains(*p));
+///     for &j in &vor.neighbors[i] {
+///         assert!(vor.neighbors[j].contains(&i));
+///     }
+/// }
+///
+/// // the copies of a site do not steal its cell nor its neighbors
+/// let pts = [v(15, 5), v(5, 5), v(10, 8), v(15, 5), v(5, 5), v(15, 5)];
+/// let vor = voronoi(&pts, &bbox);
+/// assert!((Geometry::from(&vor).area() - bbox.area()).abs() < 1e-9);
+/// assert!(vor.cell(0).contains(v(18, 2)));
+/// assert!(vor.cell(1).contains(v(2, 2)));
+/// assert!(vor.cell(2).contains(v(10, 9)));
+/// assert!((3..6).all(|i| vor.cell(i).paths().is_empty()));
+/// assert_eq!(vor.neighbors[0], vec![1, 2]);
+/// assert_eq!(vor.neighbors[1], vec![0, 2]);
+/// assert_eq!(vor.neighbors[2], vec![0, 1]);
+///
+/// // collinear sites do not have a triangulation
+/// let pts = [v(18, 9), v(2, 1), v(10, 5), v(2, 1), v(6, 3), v(18, 9)];
+/// let vor = voronoi(&pts, &bbox);
+/// assert!((Geometry::from(&vor).area() - bbox.area()).abs() < 1e-9);
+/// assert!(vor.cell(3).paths().is_empty() && vor.cell(5).paths().is_empty());
+/// assert_eq!(vor.neighbors, vec![vec![2], vec![4], vec![0, 4], vec![], vec![1, 2], vec![]]);
+/// for p in (0..20).flat_map(|x| (0..10).map(move |y| v(x, y) + v(0.25, 0.25))) {
+///     let closest = (0..pts.len()).min_by(|&i, &j| p.dist(pts[i]).total_cmp(&p.dist(pts[j])));
+///     assert!(vor.cell(closest.unwrap()).contains(p));
+/// }
+/// ```
+pub fn voronoi(pts: &[V], clip: &Rect) -> VoronoiDiagram {
+    let mut border = clip.closed_path();
+    border.make_ccw();
+    border.pop();
+
+    // duplicated sites share the cell of their first occurrence, -0.0 and 0.0
+    // are made the same by adding 0.0
+    let mut first = HashMap::with_capacity(pts.len());
+    let rep = pts
         .iter()
-        .map(|vv| voronoice::Point { x: vv.x, y: vv.y })
+        .enumerate()
+        .map(|(i, p)| {
+            *first
+                .entry(((p.x + 0.0).to_bits(), (p.y + 0.0).to_bits()))
+                .or_insert(i)
+        })
         .collect::<Vec<_>>();
 
-    let c = clip.center();
-    voronoice::VoronoiBuilder::default()
-        .set_sites(pts)
-        .set_bounding_box(voronoice::BoundingBox::new(
-            voronoice::Point { x: c.x, y: c.y },
-            clip.width(),
-            clip.height(),
-        ))
-        .build()
-        .map_or_else(Geometry::new, |vor| {
-            Geometry::from_paths(
-                vor.iter_cells()
-                    .map(|p| Path::from(p.iter_vertices().map(|p| V::new(p.x, p.y))).closed())
-                    .collect::<Vec<_>>(),
+    let tri = triangulate(pts);
+    let mut neighbors = vec![vec![]; pts.len()];
+    if tri.is_empty() {
+        // collinear sites do not have a triangulation, but each of them is a
+        // neighbor only of the sites right before and after it on the line
+        let mut order = (0..pts.len()).filter(|&i| rep[i] == i).collect::<Vec<_>>();
+        order.sort_by(|&a, &b| (pts[a].x.total_cmp(&pts[b].x)).then(pts[a].y.total_cmp(&pts[b].y)));
+        for w in order.windows(2) {
+            neighbors[w[0]].push(w[1]);
+            neighbors[w[1]].push(w[0]);
+        }
+    } else {
+        // delaunator keeps an arbitrary copy of the duplicated sites, so
+        // merge the neighbors of all the copies
+        for (i, ns) in tri.vertex_neighbors().into_iter().enumerate() {
+            let ri = rep[i];
+            neighbors[ri].extend(ns.into_iter().map(|j| rep[j]).filter(|&j| j != ri));
+        }
+    }
+
+    for ns in &mut neighbors {
+        ns.sort_unstable();
+        ns.dedup();
+    }
+
+    let cells = pts
+        .iter()
+        .enumerate()
+        .map(|(i, &site)| {
+            // sites closer than its tolerance to another site are dropped by
+            // delaunator too, their cells would be thinner than that anyway
+            if rep[i] != i || (!tri.is_empty() && neighbors[i].is_empty()) {
+                return vec![];
+            }
+
+            clip_cell(
+                border.iter().map(|p| (p, None)).collect(),
+                neighbors[i]
+                    .iter()
+                    .map(|&j| (j, radical_axis((site, 0.0), (pts[j], 0.0)))),
             )
         })
+        .collect();
+
+    VoronoiDiagram::from_cells(pts.to_vec(), cells)
+}
+
+/// Generate the [Voronoi diagram][0] for the given set of points clipping it to
+/// the area covered by the closed paths of the given Geometry, holes
+/// included.
+///
+/// The cells keep the mapping with the sites, but they're not necessarily
+/// convex anymore and they may even be made of disjoint parts. Sites outside
+/// of the Geometry still split the area, so their cells may not be empty
+/// either.
+///
+/// Only the cells that cross the boundary of the Geometry are clipped, and
+/// only against the boundary segments around them.
+///
+/// The edges of the diagram are made of the parts of the edges between the
+/// cells that are inside the Geometry plus the boundary of the Geometry
+/// itself.
+///
+/// [0]: https://en.wikipedia.org/wiki/Voronoi_diagram
+///
+/// ```rust
+/// # use lart::*;
+/// let frame = Geometry::from(Rect::with_dimensions(v(0, 0), 30.0, 10.0))
+///     - Rect::with_dimensions(v(12, 2), 6.0, 6.0);
+///
+/// let vor = voronoi_in(&[v(5, 5), v(25, 5), v(15, 5)], &frame);
+/// assert_eq!(vor.len(), 3);
+/// assert!((vor.cell(0).area() - 100.0).abs() < 1e-6);
+/// assert!((vor.cell(1).area() - 100.0).abs() < 1e-6);
+/// assert!((vor.cell(2).area() - 64.0).abs() < 1e-6);
+/// assert!(!vor.cell(2).contains(v(15, 5)));
+///
+/// // the cell in the middle surrounds the hole
+/// assert_eq!(vor.cell(2).paths().len(), 2);
+/// assert_eq!(vor.neighbors[0], vec![2]);
+/// assert_eq!(vor.neighbors[2], vec![0, 1]);
+///
+/// // the edges between the cells and the boundary of the frame
+/// assert_eq!(vor.inner_edges().count(), 2);
+/// assert_eq!(vor.edges().count(), 10);
+/// ```
+pub fn voronoi_in(pts: &[V], clip: &Geometry) -> VoronoiDiagram {
+    let Some(bbox) = clip.bbox() else {
+        return VoronoiDiagram {
+            sites: pts.to_vec(),
+            cells: vec![Geometry::new(); pts.len()],
+            neighbors: vec![vec![]; pts.len()],
+            edges: vec![],
+        };
+    };
+
+    let boundary = clip
+        .paths()
+        .iter()
+        .filter(|p| p.is_closed())
+        .flat_map(Path::segments)
+        .collect::<Vec<_>>();
+
+    // bucket the boundary so that each cell is tested only against the
+    // segments around it
+    let mut buckets = Buckets::new(&bbox, boundary.len());
+    for (i, &(a, b)) in boundary.iter().enumerate() {
+        buckets.insert(i, &bbox!(a, b));
+    }
+
+    let mut vor = voronoi(pts, &bbox);
+
+    for cell in &mut vor.cells {
+        let Some(outline) = cell.paths().first() else {
+            continue;
+        };
+        let Some(cell_bbox) = outline.bbox() else {
+            continue;
+        };
+
+        let crosses = buckets.query(&cell_bbox).any(|i| {
+            let s = boundary[i];
+            cell_bbox.intersection(&bbox!(s.0, s.1)).is_some()
+                && (outline.contains(s.0) || outline.segments().any(|c| seg_x_seg(c, s).is_some()))
+        });
+
+        if crosses {
+            *cell = &*cell & local_clip(&cell_bbox, &boundary, &buckets, bbox.right());
+        } else if !clip.contains(outline.polygon_centroid()) {
+            *cell = Geometry::new();
+        }
+    }
+
+    // keep only the parts of the shared edges that are inside the clip
+    // region, the cells are neighbors only if any of them is left
+    let mut edges = vec![];
+    let mut neighbors = vec![vec![]; pts.len()];
+    for (a, b, i, j) in vor.inner_edges() {
+        let start = edges.len();
+        edges.extend(
+            clip_segment((a, b), &boundary, &buckets, clip)
+                .into_iter()
+                .map(|seg| Edge {
+                    seg,
+                    sites: Some((i, j)),
+                }),
+        );
+
+        if edges.len() > start {
+            neighbors[i].push(j);
+            neighbors[j].push(i);
+        }
+    }
+    edges.extend(boundary.iter().map(|&seg| Edge { seg, sites: None }));
+
+    for n in &mut neighbors {
+        n.sort_unstable();
+        n.dedup();
+    }
+
+    vor.edges = edges;
+    vor.neighbors = neighbors;
+    vor
+}
+
+/// Generate the [power diagram][0], also known as Laguerre-Voronoi diagram,
+/// of the given circles clipping it to the given rect.
+///
+/// The power diagram is a weighted Voronoi diagram where each point belongs
+/// to the cell of the circle it has the lowest power distance from, that is
+/// `dist(p, center)^2 - radius^2`. The edges between the cells lie on the
+/// radical axes of the circles, so that bigger circles get bigger cells and
+/// circles that do not overlap are always separated by their edge. The cells
+/// are convex, but they may not contain their center or even be empty.
+///
+/// Circles with the same radius get the same cells as `voronoi`, the output
+/// of `CirclePacker::circles` can be used as is to get a tight tessellation
+/// around the packed circles.
+///
+/// The cells are computed by clipping the rect with the radical axes of the
+/// closest circles first, until no other circle can reach them.
+///
+/// [0]: https://en.wikipedia.org/wiki/Power_diagram
+///
+/// ```rust
+/// # use lart::*;
+/// let bbox = Rect::with_dimensions(v(0, 0), 20.0, 10.0);
+///
+/// let vor = power_diagram(&[(v(5, 5), 4.0), (v(15, 5), 1.0)], &bbox);
+/// assert_eq!(vor.len(), 2);
+/// assert_eq!(vor.sites, vec![v(5, 5), v(15, 5)]);
+/// assert!((vor.cell(0).area() - 107.5).abs() < 1e-9);
+/// assert!((vor.cell(1).area() - 92.5).abs() < 1e-9);
+/// assert_eq!(vor.neighbors, vec![vec![1], vec![0]]);
+///
+/// // the big circles swallow the site of the small one in the middle
+/// let vor = power_diagram(&[(v(4, 5), 7.0), (v(10, 5), 1.0), (v(16, 5), 7.0)], &bbox);
+/// assert!((vor.cell(0).area() - 100.0).abs() < 1e-9);
+/// assert!(vor.cell(1).paths().is_empty());
+/// assert!((vor.cell(2).area() - 100.0).abs() < 1e-9);
+/// assert_eq!(vor.neighbors, vec![vec![2], vec![], vec![0]]);
+///
+/// let pts = [v(2, 2), v(8, 3), v(5, 8), v(15, 2), v(12, 7), v(18, 8)];
+/// let power = power_diagram(&pts.map(|p| (p, 1.0)), &bbox);
+/// let vor = voronoi(&pts, &bbox);
+/// for i in 0..pts.len() {
+///     assert!((power.cell(i).area() - vor.cell(i).area()).abs() < 1e-9);
+///     assert_eq!(power.neighbors[i], vor.neighbors[i]);
+/// }
+/// ```
+pub fn power_diagram(circles: &[(V, f64)], clip: &Rect) -> VoronoiDiagram {
+    let mut border = clip.closed_path();
+    border.make_ccw();
+    border.pop();
+
+    let centers = circles.iter().map(|&(c, _)| c).collect::<Vec<_>>();
+    let Some(centers_bbox) = bbox_union(&centers) else {
+        return VoronoiDiagram::default();
+    };
+
+    // concentric circles are hidden by the biggest one, the first one wins
+    // in case of ties
+    let mut visible = HashMap::with_capacity(circles.len());
+    for (i, &(c, r)) in circles.iter().enumerate() {
+        visible
+            .entry(((c.x + 0.0).to_bits(), (c.y + 0.0).to_bits()))
+            .and_modify(|j: &mut usize| {
+                if r > circles[*j].1 {
+                    *j = i;
+                }
+            })
+            .or_insert(i);
+    }
+    let mut hidden = vec![true; circles.len()];
+    for &i in visible.values() {
+        hidden[i] = false;
+    }
+
+    let mut buckets = Buckets::new(&centers_bbox, circles.len());
+    for (i, &c) in centers.iter().enumerate() {
+        if !hidden[i] {
+            buckets.insert(i, &Rect::new(c));
+        }
+    }
+
+    let max_r2 = circles.iter().map(|(_, r)| r * r).fold(0.0, f64::max);
+
+    let cells = circles
+        .iter()
+        .enumerate()
+        .map(|(i, &(c, r))| {
+            if hidden[i] {
+                return vec![];
+            }
+
+            let home = buckets.bucket(c);
+            let mut cell = border.iter().map(|p| (p, None)).collect::<Vec<_>>();
+            for ring in 0..buckets.rings() {
+                if cell.is_empty() {
+                    break;
+                }
+
+                // the circles in this ring and in the next ones are at least
+                // d away from the center, so their radical axes are at least
+                // bound away from it because such bound grows with d, stop
+                // as soon as they cannot reach the cell anymore
+                let d = ring.saturating_sub(1) as f64 * buckets.cell;
+                let reach = cell.iter().map(|(p, _)| p.dist(c)).fold(0.0, f64::max);
+                if d > 0.0 && (d * d + r * r - max_r2) / (2.0 * d) > reach {
+                    break;
+                }
+
+                for j in buckets.ring(home, ring) {
+                    if j != i {
+                        cell = clip_cell(cell, [(j, radical_axis((c, r), circles[j]))]);
+                    }
+                }
+            }
+
+            cell
+        })
+        .collect();
+
+    VoronoiDiagram::from_cells(centers, cells)
+}
+
+/// A uniform grid of buckets over a rect, where each bucket holds the indices
+/// of the items that overlap it.
+struct Buckets {
+    bbox: Rect,
+    cell: f64,
+    grid: Grid<Vec<usize>>,
+}
+
+impl Buckets {
+    /// Create about `n` empty buckets covering the given rect.
+    fn new(bbox: &Rect, n: usize) -> Self {
+        let n = n.max(1) as f64;
+        let cell = (bbox.area() / n)
+            .sqrt()
+            .max(bbox.width().max(bbox.height()) / n)
+            .max(f64::EPSILON);
+
+        Self {
+            bbox: bbox.clone(),
+            cell,
+            grid: Grid::new(
+                vec![],
+                (bbox.width() / cell).ceil().max(1.0) as usize,
+                (bbox.height() / cell).ceil().max(1.0) as usize,
+            ),
+        }
+    }
+
+    /// Return the number of rings around any bucket needed to cover all the
+    /// buckets.
+    fn rings(&self) -> usize {
+        self.grid.width().max(self.grid.height())
+    }
+
+    /// Return the bucket the given point falls in, points outside of the
+    /// grid fall in the closest bucket.
+    fn bucket(&self, p: V) -> (usize, usize) {
+        let x = ((p.x - self.bbox.left()) / self.cell).floor().max(0.0) as usize;
+        let y = ((p.y - self.bbox.top()) / self.cell).floor().max(0.0) as usize;
+        (x.min(self.grid.width() - 1), y.min(self.grid.height() - 1))
+    }
+
+    /// Add the item with the given bbox to all the buckets it overlaps.
+    fn insert(&mut self, i: usize, bbox: &Rect) {
+        let ((x0, y0), (x1, y1)) = (self.bucket(bbox.min()), self.bucket(bbox.max()));
+        for y in y0..=y1 {
+            for x in x0..=x1 {
+                self.grid[(x, y)].push(i);
+            }
+        }
+    }
+
+    /// Return the items in the buckets overlapping the given rect, the
+    /// items in more than one of such buckets are returned more than once.
+    fn query(&self, bbox: &Rect) -> impl Iterator<Item = usize> + '_ {
+        let ((x0, y0), (x1, y1)) = (self.bucket(bbox.min()), self.bucket(bbox.max()));
+        (y0..=y1)
+            .flat_map(move |y| (x0..=x1).map(move |x| (x, y)))
+            .flat_map(|xy| self.grid[xy].iter().copied())
+    }
+
+    /// Return the items in the buckets that are exactly `ring` buckets away
+    /// from the given one either horizontally or vertically.
+    fn ring(&self, (x, y): (usize, usize), ring: usize) -> impl Iterator<Item = usize> + '_ {
+        let (x, y, k) = (x as isize, y as isize, ring as isize);
+        (y - k..=y + k)
+            .flat_map(move |yy| {
+                // only the first and last rows are full, the other ones have
+                // only the first and last buckets
+                let step = if (yy - y).abs() == k { 1 } else { 2 * ring };
+                (x - k..=x + k).step_by(step).map(move |xx| (xx, yy))
+            })
+            .filter(|&(xx, yy)| {
+                xx >= 0
+                    && yy >= 0
+                    && (xx as usize) < self.grid.width()
+                    && (yy as usize) < self.grid.height()
+            })
+            .flat_map(|(xx, yy)| self.grid[(xx as usize, yy as usize)].iter().copied())
+    }
+}
+
+/// Return a Geometry that covers the same area as the one whose boundary is
+/// made of the given bucketed segments, but only inside the given rect.
+///
+/// Each segment crossing the strip on the right of the rect is turned into a
+/// quad that extends to the left of the rect, inside the rect the quads add
+/// up to the same winding numbers as the boundary.
+fn local_clip(rect: &Rect, boundary: &[(V, V)], buckets: &Buckets, right: f64) -> Geometry {
+    let strip = bbox!(v(rect.left(), rect.top()), v(right, rect.bottom()));
+    let left = rect.left() - 1.0;
+
+    let mut segments = buckets.query(&strip).collect::<Vec<_>>();
+    segments.sort_unstable();
+    segments.dedup();
+
+    Geometry::from_paths(
+        segments
+            .into_iter()
+            .map(|i| boundary[i])
+            .filter(|&(a, b)| a.y != b.y && strip.intersection(&bbox!(a, b)).is_some())
+            .map(|(a, b)| polygon!(a, b, v(left, b.y), v(left, a.y)))
+            .collect(),
+    )
+}
+
+/// Return the parts of the given segment that are inside the given Geometry
+/// whose boundary is made of the given segments, bucketed in the given
+/// buckets.
+fn clip_segment(
+    seg: (V, V),
+    boundary: &[(V, V)],
+    buckets: &Buckets,
+    clip: &Geometry,
+) -> Vec<(V, V)> {
+    let mut ts = vec![0.0, 1.0];
+    ts.extend(
+        buckets
+            .query(&bbox!(seg.0, seg.1))
+            .filter_map(|i| seg_x_seg_t(seg, boundary[i])),
+    );
+    ts.sort_by(f64::total_cmp);
+    ts.dedup();
+
+    let mut parts: Vec<(V, V)> = vec![];
+    for w in ts.windows(2) {
+        let (a, b) = (linterp(seg.0, seg.1, w[0]), linterp(seg.0, seg.1, w[1]));
+        if a.dist(b) <= PRECISION || !clip.contains((a + b) / 2.0) {
+            continue;
+        }
+
+        match parts.last_mut() {
+            Some(last) if last.1 == a => last.1 = b,
+            _ => parts.push((a, b)),
+        }
+    }
+
+    parts
+}
+
+/// Return the half-plane containing the points whose power distance from the
+/// circle `a` is lower than the one from the circle `b` as a point on its
+/// boundary and its outward normal.
+///
+/// The boundary is the radical axis of the two circles, that is the bisector
+/// of their centers when they have the same radius.
+fn radical_axis((a, ra): (V, f64), (b, rb): (V, f64)) -> (V, V) {
+    let n = b - a;
+    (
+        a + n * ((n.norm2() + ra * ra - rb * rb) / (2.0 * n.norm2())),
+        n,
+    )
+}
+
+/// Clip the given convex polygon with the given half-planes.
+///
+/// Each vertex of the polygon is tagged with the index of the half-plane that
+/// generated the edge starting from it, if any, so that the neighbors of the
+/// cell can be tracked.
+fn clip_cell(
+    mut cell: Vec<(V, Option<usize>)>,
+    half_planes: impl IntoIterator<Item = (usize, (V, V))>,
+) -> Vec<(V, Option<usize>)> {
+    for (j, (m, n)) in half_planes {
+        if cell.is_empty() {
+            break;
+        }
+
+        let side = |p: V| (p - m).dot(n);
+
+        let mut clipped = Vec::with_capacity(cell.len() + 1);
+        for k in 0..cell.len() {
+            let (a, tag) = cell[k];
+            let b = cell[(k + 1) % cell.len()].0;
+            let (sa, sb) = (side(a), side(b));
+
+            if sa <= 0.0 {
+                clipped.push((a, tag));
+            }
+
+            if (sa <= 0.0) != (sb <= 0.0) {
+                let x = a + (b - a) * (sa / (sa - sb));
+                clipped.push((x, if sa <= 0.0 { Some(j) } else { tag }));
+            }
+        }
+
+        // drop the degenerate edges so that sites touching only at a
+        // vertex are not considered neighbors
+        clipped.dedup_by(|next, prev| {
+            let degenerate = prev.0.dist(next.0) <= PRECISION;
+            if degenerate {
+                prev.1 = next.1;
+            }
+            degenerate
+        });
+        while clipped.len() > 1 && clipped[0].0.dist(clipped[clipped.len() - 1].0) <= PRECISION {
+            clipped.pop();
+        }
+
+        if clipped.len() < 3 {
+            clipped.clear();
+        }
+        cell = clipped;
+    }
+
+    cell
 }
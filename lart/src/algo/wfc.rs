@@ -170,7 +170,7 @@ impl<'t, R: Rng> State<'t, R> {
         border_match: impl Fn(&Tile) -> bool,
     ) -> bool {
         c.and_then(|ix| self.tiles.get(self.wave[ix]))
-            .map_or(false, border_match)
+            .is_some_and(border_match)
     }
 
     fn update_entropy(&mut self, x: usize, y: usize) {
@@ -179,7 +179,7 @@ impl<'t, R: Rng> State<'t, R> {
             return;
         }
 
-        self.entropy[(x, y)] = std::f64::MAX
+        self.entropy[(x, y)] = f64::MAX
             - self
                 .entropy
                 .neighbors4(x, y)
@@ -0,0 +1,227 @@
+use crate::{linterp, v, Path, V};
+
+/// An ArcLengthIndex answers queries about the points of a Path at a given
+/// distance from its start.
+///
+/// Building the index takes linear time, but then each query only takes
+/// logarithmic time so it's worth keeping one around when many queries are
+/// made on the same Path.
+///
+/// Distances are always clamped to `0..=len()`.
+///
+/// ```rust
+/// # use lart::*;
+/// let p = path!(v(0, 0), v(10, 0), v(10, 10));
+/// let ix = ArcLengthIndex::new(&p);
+///
+/// assert_eq!(ix.len(), 20.0);
+/// assert_eq!(ix.point_at(5.0), v(5, 0));
+/// assert_eq!(ix.point_at(15.0), v(10, 5));
+/// assert_eq!(ix.point_at(100.0), v(10, 10));
+///
+/// assert_eq!(ix.tangent_at(15.0), v(0, 1));
+/// assert_eq!(ix.normal_at(5.0), v(0, 1));
+/// ```
+#[derive(Debug, Clone)]
+pub struct ArcLengthIndex<'a> {
+    path: &'a Path,
+    lengths: Vec<f64>,
+}
+
+impl<'a> ArcLengthIndex<'a> {
+    /// Create a new index for the given Path.
+    pub fn new(path: &'a Path) -> Self {
+        let mut lengths = Vec::with_capacity(path.len());
+        if !path.is_empty() {
+            lengths.push(0.0);
+        }
+
+        let mut total = 0.0;
+        for (a, b) in path.segments() {
+            total += a.dist(b);
+            lengths.push(total);
+        }
+
+        Self { path, lengths }
+    }
+
+    /// Return the total length of the Path.
+    pub fn len(&self) -> f64 {
+        self.lengths.last().copied().unwrap_or(0.0)
+    }
+
+    /// Return whether the Path has no length at all.
+    pub fn is_empty(&self) -> bool {
+        self.len() == 0.0
+    }
+
+    /// Return the index of the segment the given distance falls in together
+    /// with the parameter along such segment.
+    ///
+    /// Panics if the Path is empty.
+    pub fn locate(&self, s: f64) -> (usize, f64) {
+        assert!(
+            !self.path.is_empty(),
+            "cannot locate a point on an empty path"
+        );
+        if self.path.len() == 1 {
+            return (0, 0.0);
+        }
+
+        let s = s.clamp(0.0, self.len());
+        let i = self
+            .lengths
+            .partition_point(|&l| l <= s)
+            .saturating_sub(1)
+            .min(self.path.len() - 2);
+
+        let l = self.lengths[i + 1] - self.lengths[i];
+        let t = if l == 0.0 {
+            0.0
+        } else {
+            ((s - self.lengths[i]) / l).min(1.0)
+        };
+
+        (i, t)
+    }
+
+    /// Return the point at the given distance from the start of the Path.
+    ///
+    /// Panics if the Path is empty.
+    pub fn point_at(&self, s: f64) -> V {
+        let (i, t) = self.locate(s);
+        if t == 0.0 {
+            return self.path[i];
+        }
+        linterp(self.path[i], self.path[i + 1], t)
+    }
+
+    /// Return the unit direction of the Path at the given distance from its
+    /// start, or the zero vector if the Path has no length.
+    ///
+    /// On a vertex the direction of the segment that starts from it is
+    /// returned, except for the last vertex.
+    ///
+    /// Panics if the Path is empty.
+    pub fn tangent_at(&self, s: f64) -> V {
+        let (i, _) = self.locate(s);
+
+        let segment_dir = |j: usize| {
+            let d = self.path[j + 1] - self.path[j];
+            (d != v(0, 0)).then(|| d.normalized())
+        };
+
+        let nsegs = self.path.len().saturating_sub(1);
+        (i..nsegs)
+            .find_map(segment_dir)
+            .or_else(|| (0..i).rev().find_map(segment_dir))
+            .unwrap_or(v(0, 0))
+    }
+
+    /// Return the unit normal of the Path at the given distance from its
+    /// start, that is the tangent rotated by 90 degrees counter-clockwise in
+    /// a coordinate system where y points up.
+    ///
+    /// Panics if the Path is empty.
+    pub fn normal_at(&self, s: f64) -> V {
+        let t = self.tangent_at(s);
+        v(-t.y, t.x)
+    }
+
+    /// Return the portion of the Path between the two given distances from its
+    /// start.
+    ///
+    /// If `s1` is less than `s0` the returned Path goes backwards.
+    ///
+    /// Panics if the Path is empty.
+    pub fn subpath(&self, s0: f64, s1: f64) -> Path {
+        if s1 < s0 {
+            let mut p = self.subpath(s1, s0);
+            p.reverse();
+            return p;
+        }
+
+        let (i0, _) = self.locate(s0);
+        let (i1, _) = self.locate(s1);
+
+        let mut p = Path::with_capacity(i1 - i0 + 2);
+        p.push(self.point_at(s0));
+        for pt in self.path.iter().take(i1 + 1).skip(i0 + 1) {
+            if p.last() != Some(pt) {
+                p.push(pt);
+            }
+        }
+
+        let end = self.point_at(s1);
+        if p.len() == 1 || p.last() != Some(end) {
+            p.push(end);
+        }
+
+        p
+    }
+
+    /// Split the Path in two at the given distance from its start.
+    ///
+    /// Panics if the Path is empty.
+    pub fn split_at(&self, s: f64) -> (Path, Path) {
+        (self.subpath(0.0, s), self.subpath(s, self.len()))
+    }
+}
+
+impl Path {
+    /// Return the point at the given distance from the start of the Path.
+    ///
+    /// Prefer an ArcLengthIndex when querying the same Path many times.
+    ///
+    /// Panics if the Path is empty.
+    pub fn point_at(&self, s: f64) -> V {
+        ArcLengthIndex::new(self).point_at(s)
+    }
+
+    /// Return the unit direction of the Path at the given distance from its
+    /// start.
+    ///
+    /// Prefer an ArcLengthIndex when querying the same Path many times.
+    ///
+    /// Panics if the Path is empty.
+    pub fn tangent_at(&self, s: f64) -> V {
+        ArcLengthIndex::new(self).tangent_at(s)
+    }
+
+    /// Return the unit normal of the Path at the given distance from its
+    /// start.
+    ///
+    /// Prefer an ArcLengthIndex when querying the same Path many times.
+    ///
+    /// Panics if the Path is empty.
+    pub fn normal_at(&self, s: f64) -> V {
+        ArcLengthIndex::new(self).normal_at(s)
+    }
+
+    /// Return the portion of the Path between the two given distances from its
+    /// start.
+    ///
+    /// ```rust
+    /// # use lart::*;
+    /// let p = path!(v(0, 0), v(10, 0), v(10, 10));
+    /// assert_eq!(p.subpath(5.0, 15.0), path!(v(5, 0), v(10, 0), v(10, 5)));
+    /// assert_eq!(p.subpath(5.0, 8.0), path!(v(5, 0), v(8, 0)));
+    /// assert_eq!(p.subpath(15.0, 5.0), path!(v(10, 5), v(10, 0), v(5, 0)));
+    /// ```
+    pub fn subpath(&self, s0: f64, s1: f64) -> Path {
+        ArcLengthIndex::new(self).subpath(s0, s1)
+    }
+
+    /// Split the Path in two at the given distance from its start.
+    ///
+    /// ```rust
+    /// # use lart::*;
+    /// let p = path!(v(0, 0), v(10, 0), v(10, 10));
+    /// let (a, b) = p.split_at_length(10.0);
+    /// assert_eq!(a, path!(v(0, 0), v(10, 0)));
+    /// assert_eq!(b, path!(v(10, 0), v(10, 10)));
+    /// ```
+    pub fn split_at_length(&self, s: f64) -> (Path, Path) {
+        ArcLengthIndex::new(self).split_at(s)
+    }
+}
@@ -0,0 +1,179 @@
+use crate::{linterp, Path, V};
+
+/// The maximum number of times a curve is subdivided while flattening it, it
+/// only matters for degenerate curves or unreasonably small tolerances.
+const MAX_FLATTEN_DEPTH: u32 = 16;
+
+/// A quadratic Bezier curve that goes from `p0` to `p2` while being pulled
+/// towards the `p1` control point.
+///
+/// ```rust
+/// # use lart::*;
+/// let q = QuadBezier::new(v(0, 0), v(5, 10), v(10, 0));
+/// assert_eq!(q.eval(0.0), v(0, 0));
+/// assert_eq!(q.eval(0.5), v(5, 5));
+/// assert_eq!(q.eval(1.0), v(10, 0));
+///
+/// let p = q.flatten(0.01);
+/// assert_eq!(p.first(), Some(v(0, 0)));
+/// assert_eq!(p.last(), Some(v(10, 0)));
+/// assert!(p.iter().all(|pt| pt.y <= 5.0));
+/// ```
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub struct QuadBezier {
+    pub p0: V,
+    pub p1: V,
+    pub p2: V,
+}
+
+/// A cubic Bezier curve that goes from `p0` to `p3` while being pulled
+/// towards the `p1` and `p2` control points.
+///
+/// ```rust
+/// # use lart::*;
+/// let c = CubicBezier::new(v(0, 0), v(0, 10), v(10, 10), v(10, 0));
+/// assert_eq!(c.eval(0.5), v(5, 7.5));
+///
+/// let p = c.flatten(0.01);
+/// assert_eq!(p.first(), Some(v(0, 0)));
+/// assert_eq!(p.last(), Some(v(10, 0)));
+/// assert!(p.iter().all(|pt| pt.y <= 7.5));
+///
+/// // the smaller the tolerance the more points are needed
+/// assert!(c.flatten(0.001).len() > p.len());
+/// ```
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub struct CubicBezier {
+    pub p0: V,
+    pub p1: V,
+    pub p2: V,
+    pub p3: V,
+}
+
+impl QuadBezier {
+    /// Create a new quadratic Bezier curve with the given endpoints and
+    /// control point.
+    pub const fn new(p0: V, p1: V, p2: V) -> Self {
+        Self { p0, p1, p2 }
+    }
+
+    /// Return the point on the curve at parameter `t` in `0..=1`.
+    pub fn eval(&self, t: f64) -> V {
+        let a = linterp(self.p0, self.p1, t);
+        let b = linterp(self.p1, self.p2, t);
+        linterp(a, b, t)
+    }
+
+    /// Split the curve at parameter `t` in two curves that together trace the
+    /// same shape.
+    pub fn split(&self, t: f64) -> (Self, Self) {
+        let a = linterp(self.p0, self.p1, t);
+        let b = linterp(self.p1, self.p2, t);
+        let m = linterp(a, b, t);
+        (Self::new(self.p0, a, m), Self::new(m, b, self.p2))
+    }
+
+    /// Return the equivalent cubic Bezier curve.
+    pub fn to_cubic(&self) -> CubicBezier {
+        CubicBezier::new(
+            self.p0,
+            linterp(self.p0, self.p1, 2.0 / 3.0),
+            linterp(self.p2, self.p1, 2.0 / 3.0),
+            self.p2,
+        )
+    }
+
+    /// Approximate the curve with a Path whose points never deviate from the
+    /// curve more than the given tolerance.
+    pub fn flatten(&self, tolerance: f64) -> Path {
+        let mut p = Path::from([self.p0]);
+        self.flatten_into(&mut p, tolerance);
+        p
+    }
+
+    /// Append the flattened curve to the given Path, the starting point of
+    /// the curve is not added as it's assumed to already be the last point of
+    /// the Path.
+    pub fn flatten_into(&self, path: &mut Path, tolerance: f64) {
+        self.flatten_rec(path, tolerance, MAX_FLATTEN_DEPTH);
+    }
+
+    /// Return an upper bound of the distance between the curve and the chord
+    /// joining its endpoints.
+    fn flatness(&self) -> f64 {
+        (self.p0 - 2.0 * self.p1 + self.p2).norm() / 4.0
+    }
+
+    fn flatten_rec(&self, path: &mut Path, tolerance: f64, depth: u32) {
+        if depth == 0 || self.flatness() <= tolerance {
+            path.push(self.p2);
+            return;
+        }
+
+        let (a, b) = self.split(0.5);
+        a.flatten_rec(path, tolerance, depth - 1);
+        b.flatten_rec(path, tolerance, depth - 1);
+    }
+}
+
+impl CubicBezier {
+    /// Create a new cubic Bezier curve with the given endpoints and control
+    /// points.
+    pub const fn new(p0: V, p1: V, p2: V, p3: V) -> Self {
+        Self { p0, p1, p2, p3 }
+    }
+
+    /// Return the point on the curve at parameter `t` in `0..=1`.
+    pub fn eval(&self, t: f64) -> V {
+        let a = linterp(self.p0, self.p1, t);
+        let b = linterp(self.p1, self.p2, t);
+        let c = linterp(self.p2, self.p3, t);
+        linterp(linterp(a, b, t), linterp(b, c, t), t)
+    }
+
+    /// Split the curve at parameter `t` in two curves that together trace the
+    /// same shape.
+    pub fn split(&self, t: f64) -> (Self, Self) {
+        let a = linterp(self.p0, self.p1, t);
+        let b = linterp(self.p1, self.p2, t);
+        let c = linterp(self.p2, self.p3, t);
+        let ab = linterp(a, b, t);
+        let bc = linterp(b, c, t);
+        let m = linterp(ab, bc, t);
+        (Self::new(self.p0, a, ab, m), Self::new(m, bc, c, self.p3))
+    }
+
+    /// Approximate the curve with a Path whose points never deviate from the
+    /// curve more than the given tolerance.
+    pub fn flatten(&self, tolerance: f64) -> Path {
+        let mut p = Path::from([self.p0]);
+        self.flatten_into(&mut p, tolerance);
+        p
+    }
+
+    /// Append the flattened curve to the given Path, the starting point of
+    /// the curve is not added as it's assumed to already be the last point of
+    /// the Path.
+    pub fn flatten_into(&self, path: &mut Path, tolerance: f64) {
+        self.flatten_rec(path, tolerance, MAX_FLATTEN_DEPTH);
+    }
+
+    /// Return an upper bound of the distance between the curve and the chord
+    /// joining its endpoints.
+    fn flatness(&self) -> f64 {
+        let d1 = (self.p0 - 2.0 * self.p1 + self.p2).norm();
+        let d2 = (self.p1 - 2.0 * self.p2 + self.p3).norm();
+        0.75 * f64::max(d1, d2)
+    }
+
+    fn flatten_rec(&self, path: &mut Path, tolerance: f64, depth: u32) {
+        if depth == 0 || self.flatness() <= tolerance {
+            path.push(self.p3);
+            return;
+        }
+
+        let (a, b) = self.split(0.5);
+        a.flatten_rec(path, tolerance, depth - 1);
+        b.flatten_rec(path, tolerance, depth - 1);
+    }
+}
@@ -1,58 +1,302 @@
+use cxx::UniquePtr;
+
 use crate::{
-    ffi::{self, new_clipper},
-    Geometry,
+    ffi::{self, new_clipper, Clipper},
+    BufferOptions, EndType, FillRule, Geometry, JoinType, Path, Rect,
 };
 
+/// A BoolOperand is anything that can take part in a boolean operation as
+/// either a subject or a clip.
+///
+/// All the combinations of Geometry, Path and Rect support the `|`, `&`, `-`
+/// and `^` operators both by value and by reference.
+///
+/// ```rust
+/// # use lart::*;
+/// let square = Rect::with_dimensions(v(0, 0), 10.0, 10.0);
+/// let line = path!(v(-5, 5), v(15, 5));
+///
+/// let clipped = &line & &square;
+/// assert_eq!(clipped.paths().len(), 1);
+/// let bbox = clipped.bbox().unwrap();
+/// assert!(bbox.min().almost_equal(v(0, 5)));
+/// assert!(bbox.max().almost_equal(v(10, 5)));
+///
+/// let g = polygon!(v(0, 0), v(20, 0), v(20, 10)) - square;
+/// assert!(g.bbox().unwrap().min().x >= 10.0);
+/// ```
+pub trait BoolOperand {
+    /// Call the given function for each path of the operand.
+    fn for_each_path(&self, f: impl FnMut(&Path));
+}
+
+impl BoolOperand for Geometry {
+    fn for_each_path(&self, f: impl FnMut(&Path)) {
+        self.paths.iter().for_each(f);
+    }
+}
+
+impl BoolOperand for Path {
+    fn for_each_path(&self, mut f: impl FnMut(&Path)) {
+        f(self);
+    }
+}
+
+impl BoolOperand for Rect {
+    fn for_each_path(&self, mut f: impl FnMut(&Path)) {
+        f(&self.closed_path());
+    }
+}
+
+impl Default for BufferOptions {
+    /// Round joins and round ends with a miter limit of 2 and an automatic arc
+    /// tolerance.
+    fn default() -> Self {
+        Self {
+            join_type: JoinType::Round,
+            end_type: EndType::Round,
+            miter_limit: 2.0,
+            arc_tolerance: 0.0,
+        }
+    }
+}
+
+impl BufferOptions {
+    /// Set how the corners of the paths are joined.
+    pub const fn with_join_type(mut self, join_type: JoinType) -> Self {
+        self.join_type = join_type;
+        self
+    }
+
+    /// Set how the ends of open paths are capped, it has no effect on closed
+    /// paths which are always buffered as polygons.
+    pub const fn with_end_type(mut self, end_type: EndType) -> Self {
+        self.end_type = end_type;
+        self
+    }
+
+    /// Set the miter limit, only meaningful for `JoinType::Miter`.
+    pub const fn with_miter_limit(mut self, miter_limit: f64) -> Self {
+        self.miter_limit = miter_limit;
+        self
+    }
+
+    /// Set the maximum deviation of round joins and ends from the true arc.
+    pub const fn with_arc_tolerance(mut self, arc_tolerance: f64) -> Self {
+        self.arc_tolerance = arc_tolerance;
+        self
+    }
+}
+
+impl FillRule {
+    /// Return whether a region with the given winding number is inside
+    /// according to this rule.
+    pub fn is_inside(self, winding_number: i32) -> bool {
+        match self {
+            FillRule::EvenOdd => winding_number % 2 != 0,
+            FillRule::Positive => winding_number > 0,
+            FillRule::Negative => winding_number < 0,
+            _ => winding_number != 0,
+        }
+    }
+}
+
 impl Geometry {
     /// Offset the geometry by the given amount.
     ///
     /// If the delta is negative then the geometry is actually eroded by that
     /// amount.
     pub fn buffer(&self, delta: f64) -> Self {
+        self.buffer_with(delta, &BufferOptions::default())
+    }
+
+    /// Offset the geometry by the given amount using the given options to
+    /// join corners and to cap the ends of open paths.
+    ///
+    /// ```rust
+    /// # use lart::*;
+    /// let square = Geometry::from(Rect::with_dimensions(v(0, 0), 10.0, 10.0));
+    /// let opts = BufferOptions::default().with_join_type(JoinType::Miter);
+    /// let bbox = square.buffer_with(1.0, &opts).bbox().unwrap();
+    /// assert!(bbox.min().almost_equal(v(-1, -1)));
+    /// assert!(bbox.max().almost_equal(v(11, 11)));
+    ///
+    /// let line = Geometry::from(path!(v(0, 0), v(10, 0)));
+    /// let opts = BufferOptions::default().with_end_type(EndType::Butt);
+    /// let bbox = line.buffer_with(1.0, &opts).bbox().unwrap();
+    /// assert!(bbox.min().almost_equal(v(0, -1)));
+    /// assert!(bbox.max().almost_equal(v(10, 1)));
+    /// ```
+    pub fn buffer_with(&self, delta: f64, opts: &BufferOptions) -> Self {
         if delta != 0.0 {
-            ffi::buffer(self, delta)
+            ffi::buffer(self, delta, opts)
         } else {
             self.clone()
         }
     }
 
+    /// Return the Minkowski sum of this geometry with the given pattern, that
+    /// is the area swept by the pattern when its origin is moved along all the
+    /// paths of the geometry.
+    ///
+    /// The pattern is always considered closed and it's usually centered on
+    /// the origin, closed paths of the geometry are filled.
+    ///
+    /// ```rust
+    /// # use lart::*;
+    /// let nib = Rect::with_dimensions(v(0, 0), 2.0, 1.0).closed_path();
+    /// let stroke = Geometry::from(path!(v(0, 0), v(10, 0)));
+    ///
+    /// let bbox = stroke.minkowski_sum(&nib).bbox().unwrap();
+    /// assert!(bbox.min().almost_equal(v(0, 0)));
+    /// assert!(bbox.max().almost_equal(v(12, 1)));
+    /// ```
+    pub fn minkowski_sum(&self, pattern: &Path) -> Self {
+        ffi::minkowski_sum(pattern, self)
+    }
+
+    /// Return the Minkowski difference of this geometry with the given
+    /// pattern, that is the Minkowski sum with the pattern mirrored around
+    /// the origin.
+    ///
+    /// ```rust
+    /// # use lart::*;
+    /// let nib = Rect::with_dimensions(v(0, 0), 2.0, 1.0).closed_path();
+    /// let stroke = Geometry::from(path!(v(0, 0), v(10, 0)));
+    ///
+    /// let bbox = stroke.minkowski_diff(&nib).bbox().unwrap();
+    /// assert!(bbox.min().almost_equal(v(-2, -1)));
+    /// assert!(bbox.max().almost_equal(v(10, 0)));
+    /// ```
+    pub fn minkowski_diff(&self, pattern: &Path) -> Self {
+        ffi::minkowski_diff(pattern, self)
+    }
+
     /// Merge all the paths of this geometry together.
     pub fn union_all(&self) -> Self {
-        let mut clipper = new_clipper();
-
-        for p in &self.paths {
-            clipper.pin_mut().add_subject(p);
-        }
+        self.union_all_with(FillRule::NonZero)
+    }
 
-        clipper.pin_mut().union_()
+    /// Merge all the paths of this geometry together using the given
+    /// FillRule to decide which regions are inside.
+    ///
+    /// ```rust
+    /// # use lart::*;
+    /// let g = Geometry::from_paths(vec![
+    ///     Rect::with_dimensions(v(0, 0), 10.0, 10.0).closed_path(),
+    ///     Rect::with_dimensions(v(2, 2), 6.0, 6.0).closed_path(),
+    /// ]);
+    ///
+    /// // the inner square is swallowed by the outer one...
+    /// assert_eq!(g.union_all_with(FillRule::NonZero).paths().len(), 1);
+    /// // ...unless overlaps punch holes
+    /// assert_eq!(g.union_all_with(FillRule::EvenOdd).paths().len(), 2);
+    /// ```
+    pub fn union_all_with(&self, fill_rule: FillRule) -> Self {
+        BoolOp::new()
+            .with_fill_rule(fill_rule)
+            .subject(self)
+            .union()
     }
 }
 
-// probably not the best way to do all of this way (at least I hope so), but
-// eh...
-macro_rules! bool_op_body {
-    (Geometry, Geometry, $lhs: ident, $rhs: ident, $op: ident) => {{
-        let mut clipper = new_clipper();
+/// A BoolOp is a boolean operation between a set of subject paths and a set of
+/// clip paths.
+///
+/// The boolean operators between Geometry objects always use the
+/// `FillRule::NonZero` rule, a BoolOp allows to choose the rule explicitly.
+///
+/// ```rust
+/// # use lart::*;
+/// let a = Geometry::from(Rect::with_dimensions(v(0, 0), 10.0, 10.0));
+/// let b = Geometry::from(Rect::with_dimensions(v(5, 0), 10.0, 10.0));
+/// let g = BoolOp::new()
+///     .with_fill_rule(FillRule::EvenOdd)
+///     .subject(&a)
+///     .clip(&b)
+///     .intersection();
+/// let bbox = g.bbox().unwrap();
+/// assert!(bbox.min().almost_equal(v(5, 0)));
+/// assert!(bbox.max().almost_equal(v(10, 10)));
+/// ```
+pub struct BoolOp {
+    clipper: UniquePtr<Clipper>,
+}
 
-        for path in &$lhs.paths {
-            clipper.pin_mut().add_subject(path);
+impl BoolOp {
+    /// Create a new BoolOp without any subject nor clip that uses the
+    /// `FillRule::NonZero` rule.
+    pub fn new() -> Self {
+        Self {
+            clipper: new_clipper(),
         }
+    }
 
-        for clip in &$rhs.paths {
-            clipper.pin_mut().add_clip(clip);
-        }
+    /// Set the FillRule used for both the subject and clip paths.
+    pub fn with_fill_rule(mut self, fill_rule: FillRule) -> Self {
+        self.clipper.pin_mut().set_fill_rule(fill_rule);
+        self
+    }
+
+    /// Add all the paths of the given operand as subjects of the operation.
+    ///
+    /// Open paths are supported as subjects and they're clipped as lines.
+    pub fn subject(mut self, g: &impl BoolOperand) -> Self {
+        g.for_each_path(|p| self.clipper.pin_mut().add_subject(p));
+        self
+    }
+
+    /// Add all the paths of the given operand as clips of the operation.
+    ///
+    /// Clip paths are always considered closed.
+    pub fn clip(mut self, g: &impl BoolOperand) -> Self {
+        g.for_each_path(|p| self.clipper.pin_mut().add_clip(p));
+        self
+    }
+
+    /// Return the union of the subjects and the clips.
+    pub fn union(mut self) -> Geometry {
+        self.clipper.pin_mut().union_()
+    }
+
+    /// Return the regions that are covered by both the subjects and the clips.
+    pub fn intersection(mut self) -> Geometry {
+        self.clipper.pin_mut().intersection()
+    }
 
-        clipper.pin_mut().$op()
-    }};
+    /// Return the regions of the subjects that are not covered by the clips.
+    pub fn difference(mut self) -> Geometry {
+        self.clipper.pin_mut().difference()
+    }
+
+    /// Return the regions that are covered by either the subjects or the
+    /// clips, but not both.
+    pub fn symmetric_difference(mut self) -> Geometry {
+        self.clipper.pin_mut().symmetric_difference()
+    }
 }
 
+impl Default for BoolOp {
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+// probably not the best way to do all of this way (at least I hope so), but
+// eh...
 macro_rules! bool_op {
     ($tr: ident, $fun_name: ident, $op: ident) => {
         bool_op!(Geometry, Geometry, $tr, $fun_name, $op);
-        // bool_op!(Path, Geometry, $tr, $fun_name, $op);
+        bool_op!(Geometry, Path, $tr, $fun_name, $op);
+        bool_op!(Geometry, Rect, $tr, $fun_name, $op);
+
+        bool_op!(Path, Geometry, $tr, $fun_name, $op);
+        bool_op!(Path, Path, $tr, $fun_name, $op);
+        bool_op!(Path, Rect, $tr, $fun_name, $op);
 
-        // bool_op!(Geometry, Path, $tr, $fun_name, $op);
-        // bool_op!(Path, Path, $tr, $fun_name, $op);
+        bool_op!(Rect, Geometry, $tr, $fun_name, $op);
+        bool_op!(Rect, Path, $tr, $fun_name, $op);
+        bool_op!(Rect, Rect, $tr, $fun_name, $op);
     };
 
     ($t: ident, $arg: ident, $tr: ident, $fun_name: ident, $op: ident) => {
@@ -84,13 +328,13 @@ macro_rules! bool_op {
             type Output = Geometry;
 
             fn $fun_name(self, rhs: &'a $arg) -> Self::Output {
-                bool_op_body!($t, $arg, self, rhs, $op)
+                BoolOp::new().subject(self).clip(rhs).$op()
             }
         }
     };
 }
 
-bool_op!(BitOr, bitor, union_);
+bool_op!(BitOr, bitor, union);
 bool_op!(BitAnd, bitand, intersection);
 bool_op!(Sub, sub, difference);
 bool_op!(BitXor, bitxor, symmetric_difference);
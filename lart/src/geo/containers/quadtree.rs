@@ -1,4 +1,4 @@
-use crate::{Rect, V};
+use crate::{Rect, PRECISION, V};
 
 #[derive(Debug, Clone)]
 pub struct QuadTree<T: QuadTreeElem> {
@@ -9,11 +9,16 @@ pub trait QuadTreeElem: std::fmt::Debug {
     fn reference(&self) -> V;
 }
 
-#[derive(Debug, Clone)]
+impl QuadTreeElem for V {
+    fn reference(&self) -> V {
+        *self
+    }
+}
 
+#[derive(Debug, Clone)]
 enum Node<T: QuadTreeElem> {
     Leaf(Vec<T>, Rect),
-    Branch([Box<Node<T>>; 4]),
+    Branch([Box<Node<T>>; 4], Rect),
 }
 
 impl<T: QuadTreeElem> QuadTree<T> {
@@ -23,6 +28,65 @@ impl<T: QuadTreeElem> QuadTree<T> {
         }
     }
 
+    /// Add the given element to the QuadTree splitting the leaf it falls
+    /// into if it's too crowded.
+    ///
+    /// Like in `QuadTree::new` the element should lie inside the bbox of the
+    /// QuadTree.
+    ///
+    /// ```rust
+    /// # use lart::*;
+    /// let mut index = QuadTree::new(Rect::with_dimensions(v(0, 0), 100.0, 100.0), vec![]);
+    /// for i in 0..100 {
+    ///     index.insert(v(i, i));
+    /// }
+    ///
+    /// let mut near = index.in_range(v(50, 51), 1.5).into_iter().copied().collect::<Vec<_>>();
+    /// near.sort_by(|a, b| a.x.total_cmp(&b.x));
+    /// assert_eq!(near, vec![v(50, 50), v(51, 51)]);
+    /// ```
+    pub fn insert(&mut self, e: T) {
+        let mut node = &mut *self.root;
+        loop {
+            match node {
+                Node::Branch(sub, bbox) => {
+                    let i = Node::<T>::quadrant(bbox, e.reference());
+                    node = &mut *sub[i];
+                }
+                Node::Leaf(elements, bbox) => {
+                    elements.push(e);
+                    if elements.len() > Node::<T>::LEAF_CAPACITY {
+                        let bbox = bbox.clone();
+                        let elements = std::mem::take(elements);
+                        *node = Node::new(bbox, elements);
+                    }
+                    return;
+                }
+            }
+        }
+    }
+
+    /// Return all the elements whose reference point is at most `eps` away
+    /// from the given point.
+    ///
+    /// ```rust
+    /// # use lart::*;
+    /// #[derive(Debug, PartialEq)]
+    /// struct Site(V);
+    ///
+    /// impl QuadTreeElem for Site {
+    ///     fn reference(&self) -> V {
+    ///         self.0
+    ///     }
+    /// }
+    ///
+    /// let bbox = Rect::with_dimensions(v(0, 0), 100.0, 100.0);
+    /// let index = QuadTree::new(bbox, (0..100).map(|i| Site(v(i, i))).collect());
+    ///
+    /// // the leaves used to be compared against `eps` instead of its square,
+    /// // missing the site in the leaf next door
+    /// assert_eq!(index.in_range(v(48, 52), 3.0), vec![&Site(v(50, 50))]);
+    /// ```
     pub fn in_range(&self, p: V, eps: f64) -> Vec<&T> {
         let eps2 = eps.powi(2);
 
@@ -30,9 +94,13 @@ impl<T: QuadTreeElem> QuadTree<T> {
         let mut stack = vec![&self.root];
         while let Some(n) = stack.pop() {
             match &**n {
-                Node::Branch(sub) => stack.extend(sub.iter()),
+                Node::Branch(sub, bbox) => {
+                    if bbox.dist2(p) <= eps2 {
+                        stack.extend(sub.iter());
+                    }
+                }
                 Node::Leaf(elements, bbox) => {
-                    if !bbox.contains(p) && bbox.dist2(p) > eps {
+                    if bbox.dist2(p) > eps2 {
                         continue;
                     }
 
@@ -49,22 +117,28 @@ impl<T: QuadTreeElem> Node<T> {
     pub const LEAF_CAPACITY: usize = 64;
 
     pub fn new(bbox: Rect, elements: Vec<T>) -> Self {
-        if elements.len() <= Self::LEAF_CAPACITY {
+        // coincident elements cannot be split apart
+        let tiny = bbox.width() <= PRECISION && bbox.height() <= PRECISION;
+        if elements.len() <= Self::LEAF_CAPACITY || tiny {
             return Self::Leaf(elements, bbox);
         }
 
         let sub: [Rect; 4] = TryFrom::try_from(bbox.subdivide(2, 2).collect::<Vec<_>>()).unwrap();
         let mut sub = sub.map(|b| (b, Vec::with_capacity(Self::LEAF_CAPACITY)));
 
-        let c = bbox.center();
         for e in elements {
-            let p = e.reference();
-            let mut i = 0;
-            i += if p.x < c.x { 0 } else { 1 };
-            i += if p.y < c.y { 0 } else { 2 };
+            let i = Self::quadrant(&bbox, e.reference());
             sub[i].1.push(e);
         }
 
-        Self::Branch(sub.map(|(b, els)| Box::new(Self::new(b, els))))
+        Self::Branch(sub.map(|(b, els)| Box::new(Self::new(b, els))), bbox)
+    }
+
+    fn quadrant(bbox: &Rect, p: V) -> usize {
+        let c = bbox.center();
+        let mut i = 0;
+        i += if p.x < c.x { 0 } else { 1 };
+        i += if p.y < c.y { 0 } else { 2 };
+        i
     }
 }
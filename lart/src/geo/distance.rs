@@ -0,0 +1,222 @@
+use crate::{bbox, linterp, seg_closest_point, seg_closest_point_t, seg_x_seg, Geometry, Path, V};
+
+impl Path {
+    /// Return the point of the Path closest to the given point together with
+    /// its distance from the start of the Path along the Path itself and its
+    /// distance from the given point.
+    ///
+    /// Return None if the Path is empty.
+    ///
+    /// ```rust
+    /// # use lart::*;
+    /// let p = path!(v(0, 0), v(10, 0), v(10, 10));
+    /// assert_eq!(p.closest_point(v(12, 4)), Some((v(10, 4), 14.0, 2.0)));
+    /// assert_eq!(p.closest_point(v(-3, -4)), Some((v(0, 0), 0.0, 5.0)));
+    /// assert_eq!(Path::new().closest_point(v(0, 0)), None);
+    /// ```
+    pub fn closest_point(&self, p: V) -> Option<(V, f64, f64)> {
+        let first = self.first()?;
+        let mut best = (first, 0.0, first.dist2(p));
+
+        let mut s = 0.0;
+        for seg in self.segments() {
+            let l = seg.0.dist(seg.1);
+            let t = seg_closest_point_t(seg, p);
+            let q = linterp(seg.0, seg.1, t);
+            let d2 = q.dist2(p);
+            if d2 < best.2 {
+                best = (q, s + t * l, d2);
+            }
+            s += l;
+        }
+
+        Some((best.0, best.1, best.2.sqrt()))
+    }
+
+    /// Return the distance of the given point from the Path, infinity if the
+    /// Path is empty.
+    pub fn distance(&self, p: V) -> f64 {
+        self.closest_point(p).map_or(f64::INFINITY, |(_, _, d)| d)
+    }
+
+    /// Return the discrete [Hausdorff distance][0] between this Path and the
+    /// other one, that is how far the farthest vertex of one Path is from the
+    /// other Path.
+    ///
+    /// The farthest point might not be a vertex, so this never exceeds the
+    /// exact distance, `Path::sampled` can be used beforehand to get closer
+    /// to it.
+    ///
+    /// [0]: https://en.wikipedia.org/wiki/Hausdorff_distance
+    ///
+    /// ```rust
+    /// # use lart::*;
+    /// let a = path!(v(0, 0), v(10, 0));
+    /// let b = path!(v(0, 1), v(5, 3), v(10, 1));
+    /// assert_eq!(a.discrete_hausdorff_distance(&b), 3.0);
+    /// assert_eq!(b.discrete_hausdorff_distance(&a), 3.0);
+    /// assert_eq!(a.discrete_hausdorff_distance(&a), 0.0);
+    /// ```
+    pub fn discrete_hausdorff_distance(&self, other: &Path) -> f64 {
+        let directed = |a: &Path, b: &Path| a.iter().map(|p| b.distance(p)).fold(0.0, f64::max);
+        f64::max(directed(self, other), directed(other, self))
+    }
+
+    /// Return the [discrete Fréchet distance][0] between this Path and the
+    /// other one computed on their vertices.
+    ///
+    /// Unlike the Hausdorff distance the Fréchet distance takes the direction
+    /// of the paths into account and so it's a better measure of how similar
+    /// two curves are.
+    ///
+    /// Return infinity if any of the paths is empty.
+    ///
+    /// [0]: https://en.wikipedia.org/wiki/Fr%C3%A9chet_distance
+    ///
+    /// ```rust
+    /// # use lart::*;
+    /// let a = path!(v(0, 0), v(5, 0), v(10, 0));
+    /// let b = path!(v(0, 1), v(5, 2), v(10, 1));
+    /// assert_eq!(a.frechet_distance(&b), 2.0);
+    ///
+    /// let mut c = a.clone();
+    /// c.reverse();
+    /// assert_eq!(a.discrete_hausdorff_distance(&c), 0.0);
+    /// assert_eq!(a.frechet_distance(&c), 10.0);
+    /// ```
+    pub fn frechet_distance(&self, other: &Path) -> f64 {
+        if self.is_empty() || other.is_empty() {
+            return f64::INFINITY;
+        }
+
+        // only the previous row of the coupling table is needed
+        let mut prev = vec![0.0; other.len()];
+        let mut cur = vec![0.0; other.len()];
+        for (i, a) in self.iter().enumerate() {
+            for (j, b) in other.iter().enumerate() {
+                let d = a.dist(b);
+                cur[j] = match (i, j) {
+                    (0, 0) => d,
+                    (0, _) => f64::max(cur[j - 1], d),
+                    (_, 0) => f64::max(prev[0], d),
+                    _ => f64::max(prev[j].min(prev[j - 1]).min(cur[j - 1]), d),
+                };
+            }
+            std::mem::swap(&mut prev, &mut cur);
+        }
+
+        prev[other.len() - 1]
+    }
+}
+
+impl Geometry {
+    /// Return the point of the Geometry closest to the given point together
+    /// with its distance from it.
+    ///
+    /// Return None if the Geometry is empty.
+    ///
+    /// ```rust
+    /// # use lart::*;
+    /// let g = Geometry::from_paths(vec![
+    ///     Rect::with_dimensions(v(0, 0), 2.0, 2.0).closed_path(),
+    ///     path!(v(5, 0), v(5, 10)),
+    /// ]);
+    /// assert_eq!(g.closest_point(v(4, 1)), Some((v(5, 1), 1.0)));
+    /// assert_eq!(g.distance(v(1, 1)), 1.0);
+    /// ```
+    pub fn closest_point(&self, p: V) -> Option<(V, f64)> {
+        self.paths
+            .iter()
+            .filter_map(|path| path.closest_point(p))
+            .map(|(q, _, d)| (q, d))
+            .min_by(|(_, d0), (_, d1)| d0.total_cmp(d1))
+    }
+
+    /// Return the distance of the given point from the paths of the
+    /// Geometry, infinity if the Geometry is empty.
+    ///
+    /// Note that this is the distance from the outlines, points inside a
+    /// closed path have a positive distance too.
+    pub fn distance(&self, p: V) -> f64 {
+        self.closest_point(p).map_or(f64::INFINITY, |(_, d)| d)
+    }
+
+    /// Return the minimum distance between the paths of this Geometry and
+    /// the paths of the other one, infinity if any of them is empty.
+    ///
+    /// Like `Geometry::distance` only the outlines are considered, so a
+    /// Geometry completely inside the other one has a positive distance.
+    ///
+    /// The pairs of segments whose bboxes are farther than the closest pair
+    /// found so far are skipped, which is fast for geometries that are far
+    /// apart or made of small segments, but all the pairs are still tested
+    /// in the worst case.
+    ///
+    /// ```rust
+    /// # use lart::*;
+    /// let a = Geometry::from(Rect::with_dimensions(v(0, 0), 2.0, 2.0));
+    /// let b = Geometry::from(path!(v(5, -5), v(3, 1), v(5, 5)));
+    /// let c = Geometry::from(path!(v(1, -5), v(1, 5)));
+    /// assert_eq!(a.distance_to(&b), 1.0);
+    /// assert_eq!(a.distance_to(&c), 0.0);
+    /// ```
+    pub fn distance_to(&self, other: &Geometry) -> f64 {
+        let segments = |g: &Geometry| {
+            g.paths
+                .iter()
+                .flat_map(|p| {
+                    let single = (p.len() == 1).then(|| (p[0], p[0]));
+                    p.segments().chain(single)
+                })
+                .collect::<Vec<_>>()
+        };
+
+        let (sa, mut sb) = (segments(self), segments(other));
+
+        // sorting the segments by their leftmost x allows to stop as soon as
+        // the remaining ones are too far to the right
+        sb.sort_by(|s0, s1| s0.0.x.min(s0.1.x).total_cmp(&s1.0.x.min(s1.1.x)));
+
+        let mut best = f64::INFINITY;
+        for &a in &sa {
+            let ra = bbox!(a.0, a.1);
+            for &b in &sb {
+                let rb = bbox!(b.0, b.1);
+                let dx = f64::max(rb.left() - ra.right(), ra.left() - rb.right()).max(0.0);
+                if rb.left() > ra.right() && dx * dx >= best {
+                    break;
+                }
+
+                let dy = f64::max(rb.top() - ra.bottom(), ra.top() - rb.bottom()).max(0.0);
+                if dx * dx + dy * dy >= best {
+                    continue;
+                }
+
+                best = best.min(seg_seg_dist2(a, b));
+            }
+
+            if best == 0.0 {
+                break;
+            }
+        }
+
+        best.sqrt()
+    }
+}
+
+fn seg_seg_dist2(a: (V, V), b: (V, V)) -> f64 {
+    if seg_x_seg(a, b).is_some() {
+        return 0.0;
+    }
+
+    // when the segments don't cross the closest pair of points always
+    // involves an endpoint
+    [
+        seg_closest_point(a, b.0).dist2(b.0),
+        seg_closest_point(a, b.1).dist2(b.1),
+        seg_closest_point(b, a.0).dist2(a.0),
+        seg_closest_point(b, a.1).dist2(a.1),
+    ]
+    .into_iter()
+    .fold(f64::INFINITY, f64::min)
+}
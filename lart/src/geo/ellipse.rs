@@ -0,0 +1,154 @@
+use std::f64::consts::TAU;
+
+use crate::{Path, V};
+
+/// An Ellipse, possibly rotated or skewed, described by its center and by two
+/// conjugate semi diameters `u` and `v` so that its points are `center + u *
+/// cos(t) + v * sin(t)` for `t` in `0..TAU`.
+///
+/// Contrary to a flattened Path an Ellipse is exact, it can be transformed by
+/// any Xform and only then flattened so that the final polyline always has the
+/// desired tolerance regardless of the scale.
+///
+/// ```rust
+/// # use lart::*;
+/// let e = Ellipse::circle(v(0, 0), 10.0);
+/// let small = e.flatten(0.1);
+///
+/// let big = (e * Xform::scale(v(10, 10))).flatten(0.1);
+/// assert!(big.len() > small.len());
+/// assert!(big.iter().all(|p| (p.norm() - 100.0).abs() < 1e-9));
+/// assert!(big.segments().all(|(a, b)| ((a + b) / 2.0).norm() >= 100.0 - 0.1));
+/// ```
+#[derive(Debug, Clone, PartialEq)]
+pub struct Ellipse {
+    pub center: V,
+    pub u: V,
+    pub v: V,
+}
+
+impl Ellipse {
+    /// Create a new Ellipse with the given center and radii along its axes
+    /// rotated by the given angle in radians.
+    pub fn new(center: V, rx: f64, ry: f64, rotation: f64) -> Self {
+        Self {
+            center,
+            u: V::polar(rotation, rx),
+            v: V::polar(rotation + TAU / 4.0, ry),
+        }
+    }
+
+    /// Create a new circle with the given center and radius.
+    pub fn circle(center: V, r: f64) -> Self {
+        Self::new(center, r, r, 0.0)
+    }
+
+    /// Return the point of the Ellipse at the given parametric angle.
+    pub fn eval(&self, t: f64) -> V {
+        let (s, c) = t.sin_cos();
+        self.center + self.u * c + self.v * s
+    }
+
+    /// Return the length of the semi major axis of the Ellipse.
+    pub fn max_radius(&self) -> f64 {
+        // largest singular value of the matrix whose columns are u and v
+        let s = self.u.norm2() + self.v.norm2();
+        let det = self.u.x * self.v.y - self.u.y * self.v.x;
+        let disc = f64::max(0.0, s * s - 4.0 * det * det).sqrt();
+        ((s + disc) / 2.0).sqrt()
+    }
+
+    /// Approximate the whole Ellipse with a closed Path whose chords never
+    /// deviate from the Ellipse more than the given tolerance.
+    pub fn flatten(&self, tolerance: f64) -> Path {
+        let n = usize::max(3, arc_steps(self.max_radius(), TAU, tolerance));
+
+        let mut p = Path::with_capacity(n + 1);
+        for i in 0..n {
+            p.push(self.eval(TAU * i as f64 / n as f64));
+        }
+        p.closed()
+    }
+
+    /// Approximate the arc of the Ellipse going from parametric angle `t0` to
+    /// `t1` with an open Path whose chords never deviate from the Ellipse more
+    /// than the given tolerance.
+    ///
+    /// If `t1` is less than `t0` the arc is traversed in the opposite
+    /// direction.
+    pub fn arc(&self, t0: f64, t1: f64, tolerance: f64) -> Path {
+        let mut p = Path::from([self.eval(t0)]);
+        self.arc_into(&mut p, t0, t1, tolerance);
+        p
+    }
+
+    /// Append the flattened arc from parametric angle `t0` to `t1` to the
+    /// given Path, the starting point of the arc is not added as it's assumed
+    /// to already be the last point of the Path.
+    pub fn arc_into(&self, path: &mut Path, t0: f64, t1: f64, tolerance: f64) {
+        let n = arc_steps(self.max_radius(), t1 - t0, tolerance);
+        for i in 1..=n {
+            path.push(self.eval(t0 + (t1 - t0) * (i as f64 / n as f64)));
+        }
+    }
+}
+
+impl Path {
+    /// Create an open Path approximating the arc of the circle with the given
+    /// center and radius that goes from angle `a0` to `a1` in radians.
+    ///
+    /// The number of points is chosen so that the chords never deviate from
+    /// the true arc more than the given tolerance.
+    ///
+    /// ```rust
+    /// # use lart::*;
+    /// let p = Path::arc(v(0, 0), 10.0, 0.0, PI, 0.01);
+    /// assert!(p.first().unwrap().almost_equal(v(10, 0)));
+    /// assert!(p.last().unwrap().almost_equal(v(-10, 0)));
+    /// assert!(p.segments().all(|(a, b)| ((a + b) / 2.0).norm() >= 10.0 - 0.01));
+    /// ```
+    pub fn arc(c: V, r: f64, a0: f64, a1: f64, tolerance: f64) -> Self {
+        Ellipse::circle(c, r).arc(a0, a1, tolerance)
+    }
+
+    /// Create a closed Path approximating the ellipse with the given center
+    /// and radii rotated by the given angle in radians.
+    ///
+    /// The number of points is chosen so that the chords never deviate from
+    /// the true ellipse more than the given tolerance.
+    ///
+    /// ```rust
+    /// # use lart::*;
+    /// let p = Path::ellipse(v(0, 0), 20.0, 10.0, TAU / 4.0, 0.01);
+    /// let bbox = p.bbox().unwrap();
+    /// assert!(p.is_closed());
+    /// assert!((bbox.width() - 20.0).abs() <= 0.02);
+    /// assert!((bbox.height() - 40.0).abs() <= 0.02);
+    /// ```
+    pub fn ellipse(c: V, rx: f64, ry: f64, rotation: f64, tolerance: f64) -> Self {
+        Ellipse::new(c, rx, ry, rotation).flatten(tolerance)
+    }
+
+    /// Create a closed Path approximating the circle with the given center and
+    /// radius.
+    ///
+    /// Contrary to `Path::circle` the number of points is chosen so that the
+    /// chords never deviate from the true circle more than the given
+    /// tolerance, regardless of the radius.
+    pub fn circle_with_tolerance(c: V, r: f64, tolerance: f64) -> Self {
+        Ellipse::circle(c, r).flatten(tolerance)
+    }
+}
+
+/// Return the number of segments needed to approximate an arc of the given
+/// radius and angle so that the chords never deviate from the arc more than
+/// the given tolerance.
+fn arc_steps(r: f64, angle: f64, tolerance: f64) -> usize {
+    let max_angle = if tolerance >= r {
+        TAU / 4.0
+    } else {
+        2.0 * f64::acos(1.0 - tolerance / r)
+    };
+
+    usize::max(1, (angle.abs() / max_angle).ceil() as usize)
+}
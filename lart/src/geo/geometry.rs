@@ -1,4 +1,12 @@
-use crate::{bbox_union, Bbox, Geometry, Path, Rect};
+use crate::{bbox_union, seg_closest_point, Bbox, FillRule, Geometry, Path, Rect, Region, V};
+
+/// Where a point lies with respect to the area covered by a Geometry.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum PointLocation {
+    Inside,
+    Outside,
+    OnBoundary,
+}
 
 impl Geometry {
     /// Create an empty Geometry.
@@ -35,6 +43,84 @@ impl Geometry {
     pub fn append(&mut self, o: &Self) {
         self.paths.extend_from_slice(&o.paths);
     }
+
+    /// Return the sum of the winding numbers of the given point with respect
+    /// to all the closed paths of this Geometry.
+    pub fn winding_number(&self, p: V) -> i32 {
+        self.paths
+            .iter()
+            .filter(|path| path.is_closed())
+            .map(|path| path.winding_number(p))
+            .sum()
+    }
+
+    /// Return whether the given point is inside the area delimited by the
+    /// closed paths of this Geometry following the `FillRule::NonZero` rule
+    /// like in the boolean operations.
+    ///
+    /// ```rust
+    /// # use lart::*;
+    /// let mut hole = Rect::with_dimensions(v(2, 2), 6.0, 6.0).closed_path();
+    /// hole.reverse();
+    /// let g = Geometry::from_paths(vec![
+    ///     Rect::with_dimensions(v(0, 0), 10.0, 10.0).closed_path(),
+    ///     hole,
+    /// ]);
+    /// assert!(g.contains(v(1, 1)));
+    /// assert!(!g.contains(v(5, 5)));
+    /// assert!(!g.contains(v(11, 5)));
+    /// ```
+    pub fn contains(&self, p: V) -> bool {
+        self.contains_with(p, FillRule::NonZero)
+    }
+
+    /// Return whether the given point is inside the area delimited by the
+    /// closed paths of this Geometry following the given FillRule.
+    ///
+    /// ```rust
+    /// # use lart::*;
+    /// let g = Geometry::from_paths(vec![
+    ///     Rect::with_dimensions(v(0, 0), 10.0, 10.0).closed_path(),
+    ///     Rect::with_dimensions(v(2, 2), 6.0, 6.0).closed_path(),
+    /// ]);
+    /// assert!(g.contains_with(v(5, 5), FillRule::NonZero));
+    /// assert!(!g.contains_with(v(5, 5), FillRule::EvenOdd));
+    /// assert!(g.contains_with(v(1, 1), FillRule::EvenOdd));
+    /// ```
+    pub fn contains_with(&self, p: V, fill_rule: FillRule) -> bool {
+        fill_rule.is_inside(self.winding_number(p))
+    }
+
+    /// Classify the given point as inside, outside or on the boundary of the
+    /// area delimited by the closed paths of this Geometry.
+    ///
+    /// A point is considered on the boundary if it's at most `tolerance` away
+    /// from any of the closed paths.
+    ///
+    /// ```rust
+    /// # use lart::*;
+    /// let g = Geometry::from(Rect::with_dimensions(v(0, 0), 10.0, 10.0));
+    /// assert_eq!(g.classify(v(5, 5), 0.1), PointLocation::Inside);
+    /// assert_eq!(g.classify(v(10.05, 5), 0.1), PointLocation::OnBoundary);
+    /// assert_eq!(g.classify(v(10.5, 5), 0.1), PointLocation::Outside);
+    /// ```
+    pub fn classify(&self, p: V, tolerance: f64) -> PointLocation {
+        let tolerance2 = tolerance * tolerance;
+        let on_boundary = self
+            .paths
+            .iter()
+            .filter(|path| path.is_closed())
+            .flat_map(Path::segments)
+            .any(|s| seg_closest_point(s, p).dist2(p) <= tolerance2);
+
+        if on_boundary {
+            PointLocation::OnBoundary
+        } else if self.contains(p) {
+            PointLocation::Inside
+        } else {
+            PointLocation::Outside
+        }
+    }
 }
 
 impl Bbox for Geometry {
@@ -43,6 +129,12 @@ impl Bbox for Geometry {
     }
 }
 
+impl Region for Geometry {
+    fn contains(&self, p: V) -> bool {
+        Geometry::contains(self, p)
+    }
+}
+
 impl From<Path> for Geometry {
     fn from(p: Path) -> Self {
         Geometry { paths: vec![p] }
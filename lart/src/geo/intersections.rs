@@ -0,0 +1,562 @@
+use std::{
+    cell::Cell,
+    cmp::{Ordering, Reverse},
+    collections::{BTreeSet, BinaryHeap, HashSet},
+    rc::Rc,
+};
+
+use crate::{linterp, seg_x_line_t, v, Geometry, Path, V};
+
+/// A crossing between two segments of a Geometry.
+///
+/// `a` always refers to the segment that comes first in the Geometry, that is
+/// the one with the smallest path index or the smallest segment index if both
+/// segments belong to the same path.
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub struct Intersection {
+    pub point: V,
+    pub a: SegmentRef,
+    pub b: SegmentRef,
+}
+
+/// A reference to a point on a given segment of a Geometry.
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub struct SegmentRef {
+    /// The index of the path in the Geometry.
+    pub path: usize,
+
+    /// The index of the segment in the path, segment `i` goes from point `i`
+    /// to point `i + 1`.
+    pub segment: usize,
+
+    /// The parameter along the segment in `0..=1`.
+    pub t: f64,
+}
+
+impl Geometry {
+    /// Find all the points where the segments of this Geometry cross each
+    /// other, both between different paths and inside the same path.
+    ///
+    /// Consecutive segments of a path are not considered to intersect at the
+    /// vertex they share and a crossing that happens exactly on a vertex is
+    /// reported only once. Collinear overlapping segments are ignored.
+    ///
+    /// The crossings are found with a sweep line in `O((n + k) log n)` time,
+    /// where `n` is the number of segments and `k` the number of crossings.
+    ///
+    /// ```rust
+    /// # use lart::*;
+    /// let g = Geometry::from_paths(vec![
+    ///     path!(v(0, 0), v(10, 0)),
+    ///     path!(v(5, -5), v(5, 5), v(8, 5), v(8, -5)),
+    /// ]);
+    ///
+    /// let xs = g.intersections();
+    /// assert_eq!(xs.len(), 2);
+    ///
+    /// assert_eq!(xs[0].point, v(5, 0));
+    /// assert_eq!((xs[0].a.path, xs[0].a.segment, xs[0].a.t), (0, 0, 0.5));
+    /// assert_eq!((xs[0].b.path, xs[0].b.segment, xs[0].b.t), (1, 0, 0.5));
+    ///
+    /// assert_eq!(xs[1].point, v(8, 0));
+    /// assert_eq!((xs[1].b.path, xs[1].b.segment), (1, 2));
+    ///
+    /// // a weave of horizontal and vertical lines, the ones on the border
+    /// // touch the others at their endpoints
+    /// let weave = Geometry::from_paths(
+    ///     (0..10)
+    ///         .flat_map(|i| [path!(v(0, i), v(9, i)), path!(v(i, 0), v(i, 9))])
+    ///         .collect(),
+    /// );
+    /// assert_eq!(weave.intersections().len(), 100);
+    /// ```
+    pub fn intersections(&self) -> Vec<Intersection> {
+        intersections(&self.paths)
+    }
+}
+
+impl Path {
+    /// Find all the points where the Path crosses itself.
+    ///
+    /// All the returned Intersections refer to path `0`.
+    ///
+    /// ```rust
+    /// # use lart::*;
+    /// let bowtie = polygon!(v(0, 0), v(10, 10), v(10, 0), v(0, 10));
+    /// let xs = bowtie.self_intersections();
+    /// assert_eq!(xs.len(), 1);
+    /// assert_eq!(xs[0].point, v(5, 5));
+    /// assert_eq!((xs[0].a.segment, xs[0].b.segment), (0, 2));
+    ///
+    /// assert!(polygon!(v(0, 0), v(10, 0), v(10, 10)).self_intersections().is_empty());
+    /// ```
+    pub fn self_intersections(&self) -> Vec<Intersection> {
+        intersections(std::slice::from_ref(self))
+    }
+}
+
+#[derive(Debug, Clone, Copy)]
+struct Segment {
+    path: usize,
+    segment: usize,
+    a: V,
+    b: V,
+
+    /// The endpoints of the segment in sweep order.
+    lo: V,
+    hi: V,
+    slope: f64,
+}
+
+impl Segment {
+    fn new(path: usize, segment: usize, a: V, b: V) -> Self {
+        let (lo, hi) = if sweep_cmp(a, b) == Ordering::Less {
+            (a, b)
+        } else {
+            (b, a)
+        };
+        let slope = if lo.x == hi.x {
+            f64::INFINITY
+        } else {
+            (hi.y - lo.y) / (hi.x - lo.x)
+        };
+
+        Self {
+            path,
+            segment,
+            a,
+            b,
+            lo,
+            hi,
+            slope,
+        }
+    }
+
+    /// Return the y of the segment on the vertical line passing through the
+    /// given point, vertical segments are considered to be at the point
+    /// itself.
+    fn y_at(&self, p: V) -> f64 {
+        if self.lo.x == self.hi.x {
+            p.y.clamp(self.lo.y, self.hi.y)
+        } else if p.x <= self.lo.x {
+            self.lo.y
+        } else if p.x >= self.hi.x {
+            self.hi.y
+        } else {
+            self.lo.y + self.slope * (p.x - self.lo.x)
+        }
+    }
+}
+
+/// Find all the crossings between the segments of the given paths with a
+/// [Bentley-Ottmann][0] sweep in `O((n + k) log n)` time, where `k` is the
+/// number of crossings.
+///
+/// [0]: https://en.wikipedia.org/wiki/Bentley%E2%80%93Ottmann_algorithm
+// the order of the entries of the status follows the sweep line on purpose
+#[allow(clippy::mutable_key_type)]
+fn intersections(paths: &[Path]) -> Vec<Intersection> {
+    let mut segments = vec![];
+    for (path, p) in paths.iter().enumerate() {
+        for (segment, (a, b)) in p.segments().enumerate() {
+            if a != b {
+                segments.push(Segment::new(path, segment, a, b));
+            }
+        }
+    }
+
+    let mut starts = (0..segments.len()).collect::<Vec<_>>();
+    starts.sort_by(|&i, &j| sweep_cmp(segments[i].lo, segments[j].lo));
+    let mut endpoints = segments
+        .iter()
+        .flat_map(|s| [s.lo, s.hi])
+        .collect::<Vec<_>>();
+    endpoints.sort_by(|&p, &q| sweep_cmp(p, q));
+    endpoints.dedup();
+
+    // the rounding errors grow with the magnitude of the coordinates
+    let eps = 1e-9
+        * endpoints
+            .iter()
+            .fold(1.0, |m, p| f64::max(m, p.x.abs().max(p.y.abs())));
+
+    let line = Rc::new(SweepLine {
+        slots: vec![Cell::new(0); segments.len()],
+        segments,
+        at: Cell::new(v(f64::NEG_INFINITY, f64::NEG_INFINITY)),
+        after: Cell::new(true),
+        eps,
+    });
+    let entry = |kind| Entry {
+        kind,
+        line: Rc::clone(&line),
+    };
+
+    let mut sweep = Sweep {
+        paths,
+        line: &line,
+        crossings: BinaryHeap::new(),
+        tested: HashSet::new(),
+        out: vec![],
+    };
+    let mut status = BTreeSet::new();
+    let mut free_slots = (0..line.slots.len()).rev().collect::<Vec<_>>();
+    let (mut next_start, mut next_endpoint) = (0, 0);
+    loop {
+        let p = match (endpoints.get(next_endpoint), sweep.crossings.peek()) {
+            (None, None) => break,
+            (Some(&p), None) => p,
+            (None, Some(Reverse(q))) => q.0,
+            (Some(&p), Some(Reverse(q))) => match sweep_cmp(p, q.0) {
+                Ordering::Greater => q.0,
+                _ => p,
+            },
+        };
+        while endpoints
+            .get(next_endpoint)
+            .is_some_and(|&q| sweep_cmp(q, p) != Ordering::Greater)
+        {
+            next_endpoint += 1;
+        }
+        while sweep
+            .crossings
+            .peek()
+            .is_some_and(|Reverse(q)| sweep_cmp(q.0, p) != Ordering::Greater)
+        {
+            sweep.crossings.pop();
+        }
+        line.at.set(p);
+        line.after.set(false);
+
+        // the segments passing through the event point together with the
+        // ones starting from it all meet there, the segments right below and
+        // right above them are their neighbors
+        let alive = |e: &&Entry| sweep_cmp(line.segments[e.segment()].hi, p) == Ordering::Greater;
+        let prev = status
+            .range(..entry(EntryKind::Below))
+            .rev()
+            .find(alive)
+            .cloned();
+        let mut window = vec![];
+        let mut rest = status.range(entry(EntryKind::Below)..).peekable();
+        while let Some(e) =
+            rest.next_if(|e| line.cmp_probe(EntryKind::Above, e.segment()) == Ordering::Greater)
+        {
+            window.push(e.slot());
+        }
+        let next = rest.find(alive).cloned();
+
+        let mut here = window
+            .iter()
+            .map(|&s| line.slots[s].get())
+            .collect::<Vec<_>>();
+        while let Some(&i) = starts.get(next_start) {
+            if sweep_cmp(line.segments[i].lo, p) == Ordering::Greater {
+                break;
+            }
+            here.push(i);
+            next_start += 1;
+        }
+
+        for (k, &i) in here.iter().enumerate() {
+            for &j in &here[k + 1..] {
+                sweep.test(i, j);
+            }
+        }
+
+        // the segments that go on past the event point take the slots of
+        // the window in the order they have after it, the slots in excess
+        // are removed while they still hold their segments so that the
+        // order of the status is never broken
+        here.retain(|&i| sweep_cmp(line.segments[i].hi, p) == Ordering::Greater);
+        while window.len() > here.len() {
+            let s = window.pop().unwrap();
+            if !status.remove(&entry(EntryKind::Slot(s))) {
+                // rounding errors broke the order of the status
+                status.retain(|e| e.slot() != s);
+            }
+            free_slots.push(s);
+        }
+
+        line.after.set(true);
+        here.sort_by(|&i, &j| line.cmp_segments(i, j));
+        for (k, &i) in here.iter().enumerate() {
+            match window.get(k) {
+                Some(&s) => line.slots[s].set(i),
+                None => {
+                    let s = free_slots.pop().unwrap();
+                    line.slots[s].set(i);
+                    status.insert(entry(EntryKind::Slot(s)));
+                }
+            }
+        }
+
+        match (prev, here.first(), here.last(), next) {
+            (Some(prev), None, None, Some(next)) => {
+                sweep.test(prev.segment(), next.segment());
+            }
+            (prev, Some(&lowest), Some(&highest), next) => {
+                if let Some(prev) = prev {
+                    sweep.test(prev.segment(), lowest);
+                }
+                if let Some(next) = next {
+                    sweep.test(highest, next.segment());
+                }
+            }
+            _ => {}
+        }
+    }
+
+    let mut out = sweep.out;
+    out.sort_by(|x0, x1| {
+        (x0.a.path, x0.a.segment)
+            .cmp(&(x1.a.path, x1.a.segment))
+            .then(x0.a.t.total_cmp(&x1.a.t))
+            .then((x0.b.path, x0.b.segment).cmp(&(x1.b.path, x1.b.segment)))
+    });
+
+    out
+}
+
+/// Compare the points in the order they're met by the sweep line, that is
+/// from left to right and from bottom to top.
+fn sweep_cmp(p: V, q: V) -> Ordering {
+    p.x.total_cmp(&q.x).then(p.y.total_cmp(&q.y))
+}
+
+struct Sweep<'a> {
+    paths: &'a [Path],
+    line: &'a SweepLine,
+    crossings: BinaryHeap<Reverse<EventPoint>>,
+    tested: HashSet<u64>,
+    out: Vec<Intersection>,
+}
+
+impl Sweep<'_> {
+    /// Test the two segments for an intersection, if they were never tested
+    /// before, and schedule the event where they swap if it's yet to come.
+    fn test(&mut self, i: usize, j: usize) {
+        if !self
+            .tested
+            .insert(((i.min(j) as u64) << 32) | i.max(j) as u64)
+        {
+            return;
+        }
+
+        let (s0, s1) = (&self.line.segments[i], &self.line.segments[j]);
+        let (s0, s1) = if (s0.path, s0.segment) < (s1.path, s1.segment) {
+            (s0, s1)
+        } else {
+            (s1, s0)
+        };
+
+        // the crossings that are not reported are on the endpoints of the
+        // segments, which are events already
+        let Some(x) = intersect(self.paths, s0, s1) else {
+            return;
+        };
+        self.out.push(x);
+
+        // rounding errors can move the crossing out of the segments, e.g. a
+        // bit to the left of a vertical one, or even behind the sweep line
+        let p = self.line.at.get();
+        let (ylo, yhi) = (s0.lo.y.min(s0.hi.y), s0.lo.y.max(s0.hi.y));
+        let (y1lo, y1hi) = (s1.lo.y.min(s1.hi.y), s1.lo.y.max(s1.hi.y));
+        let q = x.point;
+        let q = v(
+            q.x.max(s0.lo.x.max(s1.lo.x))
+                .min(s0.hi.x.min(s1.hi.x))
+                .max(p.x),
+            q.y.max(ylo.max(y1lo)).min(yhi.min(y1hi)),
+        );
+        if sweep_cmp(q, p) == Ordering::Greater {
+            self.crossings.push(Reverse(EventPoint(q)));
+        }
+    }
+}
+
+#[derive(Debug, Clone, Copy)]
+struct EventPoint(V);
+
+impl PartialEq for EventPoint {
+    fn eq(&self, other: &Self) -> bool {
+        self.cmp(other) == Ordering::Equal
+    }
+}
+
+impl Eq for EventPoint {}
+
+impl PartialOrd for EventPoint {
+    fn partial_cmp(&self, other: &Self) -> Option<Ordering> {
+        Some(self.cmp(other))
+    }
+}
+
+impl Ord for EventPoint {
+    fn cmp(&self, other: &Self) -> Ordering {
+        sweep_cmp(self.0, other.0)
+    }
+}
+
+/// The state of the sweep shared by all the entries of its status.
+struct SweepLine {
+    segments: Vec<Segment>,
+
+    /// The segment held by each slot of the status, segments that swap at a
+    /// crossing swap their slots without touching the status.
+    slots: Vec<Cell<usize>>,
+
+    at: Cell<V>,
+
+    /// Whether the segments meeting at the event point have already been
+    /// swapped.
+    after: Cell<bool>,
+
+    eps: f64,
+}
+
+impl SweepLine {
+    /// Compare the two segments from bottom to top along the sweep line.
+    fn cmp_segments(&self, i: usize, j: usize) -> Ordering {
+        if i == j {
+            return Ordering::Equal;
+        }
+
+        let p = self.at.get();
+        let (si, sj) = (&self.segments[i], &self.segments[j]);
+        let (yi, yj) = (si.y_at(p), sj.y_at(p));
+        if (yi - yj).abs() > self.eps {
+            return yi.total_cmp(&yj);
+        }
+
+        // the segments meet on the sweep line, if that's below the event
+        // point they already swapped and the lower one is the one with the
+        // smallest slope, otherwise they're still in the order they had
+        // before
+        let y = (yi + yj) / 2.0;
+        let swapped = y < p.y - self.eps || (y <= p.y + self.eps && self.after.get());
+        let by_slope = si.slope.total_cmp(&sj.slope);
+        let by_slope = if swapped {
+            by_slope
+        } else {
+            by_slope.reverse()
+        };
+        by_slope.then(i.cmp(&j))
+    }
+
+    /// Compare the probe right below or right above the segments passing
+    /// through the event point with the given segment.
+    fn cmp_probe(&self, probe: EntryKind, i: usize) -> Ordering {
+        let p = self.at.get();
+        let y = self.segments[i].y_at(p);
+        let below = match probe {
+            EntryKind::Above => y <= p.y + self.eps,
+            _ => y < p.y - self.eps,
+        };
+        if below {
+            Ordering::Greater
+        } else {
+            Ordering::Less
+        }
+    }
+}
+
+/// An entry in the status of the sweep, that is the segments crossed by the
+/// sweep line ordered from bottom to top.
+#[derive(Clone)]
+struct Entry {
+    kind: EntryKind,
+    line: Rc<SweepLine>,
+}
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+enum EntryKind {
+    Slot(usize),
+
+    /// Probes right below and right above all the segments passing through
+    /// the event point.
+    Below,
+    Above,
+}
+
+impl Entry {
+    fn slot(&self) -> usize {
+        match self.kind {
+            EntryKind::Slot(s) => s,
+            EntryKind::Below | EntryKind::Above => unreachable!(),
+        }
+    }
+
+    fn segment(&self) -> usize {
+        self.line.slots[self.slot()].get()
+    }
+}
+
+impl PartialEq for Entry {
+    fn eq(&self, other: &Self) -> bool {
+        self.cmp(other) == Ordering::Equal
+    }
+}
+
+impl Eq for Entry {}
+
+impl PartialOrd for Entry {
+    fn partial_cmp(&self, other: &Self) -> Option<Ordering> {
+        Some(self.cmp(other))
+    }
+}
+
+impl Ord for Entry {
+    fn cmp(&self, other: &Self) -> Ordering {
+        match (self.kind, other.kind) {
+            (EntryKind::Slot(_), EntryKind::Slot(_)) => {
+                self.line.cmp_segments(self.segment(), other.segment())
+            }
+            (EntryKind::Slot(_), k) => self.line.cmp_probe(k, self.segment()).reverse(),
+            (k, EntryKind::Slot(_)) => self.line.cmp_probe(k, other.segment()),
+            (EntryKind::Below, EntryKind::Above) => Ordering::Less,
+            (EntryKind::Above, EntryKind::Below) => Ordering::Greater,
+            _ => Ordering::Equal,
+        }
+    }
+}
+
+fn intersect(paths: &[Path], s0: &Segment, s1: &Segment) -> Option<Intersection> {
+    if s0.path == s1.path && are_adjacent(&paths[s0.path], s0.segment, s1.segment) {
+        return None;
+    }
+
+    let t0 = seg_x_line_t((s0.a, s0.b), (s1.a, s1.b))?;
+    let t1 = seg_x_line_t((s1.a, s1.b), (s0.a, s0.b))?;
+
+    // a crossing on the end vertex of a segment is also a crossing on the
+    // start vertex of the following one, report only the latter
+    if (t0 == 1.0 && has_next(&paths[s0.path], s0.segment))
+        || (t1 == 1.0 && has_next(&paths[s1.path], s1.segment))
+    {
+        return None;
+    }
+
+    Some(Intersection {
+        point: linterp(s0.a, s0.b, t0),
+        a: SegmentRef {
+            path: s0.path,
+            segment: s0.segment,
+            t: t0,
+        },
+        b: SegmentRef {
+            path: s1.path,
+            segment: s1.segment,
+            t: t1,
+        },
+    })
+}
+
+fn are_adjacent(path: &Path, i: usize, j: usize) -> bool {
+    let (i, j) = (i.min(j), i.max(j));
+    j == i + 1 || (path.is_closed() && i == 0 && j == path.len() - 2)
+}
+
+fn has_next(path: &Path, i: usize) -> bool {
+    i + 2 < path.len() || path.is_closed()
+}
@@ -1,6 +1,7 @@
 #include "lart/src/geo/types.rs.h"
 
 #include "clipper2/clipper.h"
+#include "clipper2/clipper.minkowski.h"
 
 static Path to_path(Clipper2Lib::Path64 const &path64, double precision, bool close)
 {
@@ -31,6 +32,22 @@ static Clipper2Lib::Path64 to_path64(Path const &path, double precision)
     return path64;
 }
 
+static Clipper2Lib::FillRule to_clipper_fill_rule(FillRule fill_rule)
+{
+    switch (fill_rule)
+    {
+    case FillRule::EvenOdd:
+        return Clipper2Lib::FillRule::EvenOdd;
+    case FillRule::Positive:
+        return Clipper2Lib::FillRule::Positive;
+    case FillRule::Negative:
+        return Clipper2Lib::FillRule::Negative;
+    case FillRule::NonZero:
+    default:
+        return Clipper2Lib::FillRule::NonZero;
+    }
+}
+
 struct Clipper::pimpl
 {
     Clipper2Lib::Clipper64 clipper;
@@ -95,6 +112,11 @@ void Clipper::add_clip(Path const &polygon)
     impl->clipper.AddClip({to_path64(polygon, impl->precision)});
 }
 
+void Clipper::set_fill_rule(FillRule fill_rule)
+{
+    impl->fill_rule = to_clipper_fill_rule(fill_rule);
+}
+
 Geometry Clipper::union_()
 {
     return impl->execute(Clipper2Lib::ClipType::Union);
@@ -120,20 +142,55 @@ std::unique_ptr<Clipper> new_clipper()
     return std::make_unique<Clipper>();
 }
 
-Geometry buffer(Geometry const &geo, double delta)
+static Clipper2Lib::JoinType to_clipper_join_type(JoinType jt)
+{
+    switch (jt)
+    {
+    case JoinType::Square:
+        return Clipper2Lib::JoinType::Square;
+    case JoinType::Bevel:
+        return Clipper2Lib::JoinType::Bevel;
+    case JoinType::Miter:
+        return Clipper2Lib::JoinType::Miter;
+    case JoinType::Round:
+    default:
+        return Clipper2Lib::JoinType::Round;
+    }
+}
+
+static Clipper2Lib::EndType to_clipper_end_type(EndType et)
+{
+    switch (et)
+    {
+    case EndType::Butt:
+        return Clipper2Lib::EndType::Butt;
+    case EndType::Square:
+        return Clipper2Lib::EndType::Square;
+    case EndType::Joined:
+        return Clipper2Lib::EndType::Joined;
+    case EndType::Round:
+    default:
+        return Clipper2Lib::EndType::Round;
+    }
+}
+
+Geometry buffer(Geometry const &geo, double delta, BufferOptions const &opts)
 {
-    Clipper2Lib::ClipperOffset off;
     double precision = 1000.0;
+    Clipper2Lib::ClipperOffset off(opts.miter_limit, opts.arc_tolerance * precision);
+
+    Clipper2Lib::JoinType join_type = to_clipper_join_type(opts.join_type);
+    Clipper2Lib::EndType open_end_type = to_clipper_end_type(opts.end_type);
 
     for (auto const &path : geo.paths)
     {
         if (path.points.empty())
             continue;
 
-        Clipper2Lib::EndType end_type = Clipper2Lib::EndType::Round;
+        Clipper2Lib::EndType end_type = open_end_type;
         if (path.points[0] == path.points.back())
             end_type = Clipper2Lib::EndType::Polygon;
-        off.AddPath(to_path64(path, precision), Clipper2Lib::JoinType::Round, end_type);
+        off.AddPath(to_path64(path, precision), join_type, end_type);
     }
 
     Clipper2Lib::Paths64 paths;
@@ -146,3 +203,100 @@ Geometry buffer(Geometry const &geo, double delta)
 
     return out;
 }
+
+static void push_polygons(rust::Vec<Polygon> &out,
+                          Clipper2Lib::PolyPath64 const &outer,
+                          size_t depth,
+                          double precision)
+{
+    Polygon poly;
+    poly.exterior = to_path(outer.Polygon(), precision, true);
+    poly.depth = depth;
+
+    poly.holes.reserve(outer.Count());
+    for (auto const &hole : outer)
+        poly.holes.push_back(to_path(hole->Polygon(), precision, true));
+
+    out.push_back(std::move(poly));
+
+    for (auto const &hole : outer)
+        for (auto const &island : *hole)
+            push_polygons(out, *island, depth + 1, precision);
+}
+
+rust::Vec<Polygon> polygons(Geometry const &geo, FillRule fill_rule)
+{
+    Clipper2Lib::Clipper64 clipper;
+    double precision = 1000.0;
+
+    for (auto const &path : geo.paths)
+    {
+        // open paths cannot delimit any area
+        if (path.points.empty() || path.points[0] != path.points.back())
+            continue;
+
+        clipper.AddSubject({to_path64(path, precision)});
+    }
+
+    rust::Vec<Polygon> out;
+
+    Clipper2Lib::PolyTree64 polytree;
+    Clipper2Lib::Paths64 open_paths;
+    bool ok = clipper.Execute(Clipper2Lib::ClipType::Union, to_clipper_fill_rule(fill_rule), polytree, open_paths);
+    if (!ok)
+        return out;
+
+    for (auto const &outer : polytree)
+        push_polygons(out, *outer, 0, precision);
+
+    return out;
+}
+
+static Geometry minkowski(Path const &pattern, Geometry const &geo, bool diff)
+{
+    double precision = 1000.0;
+
+    Clipper2Lib::Path64 pattern64 = to_path64(pattern, precision);
+    // the pattern is always considered closed, drop the duplicated endpoint
+    if (pattern64.size() > 1 && pattern64[0] == pattern64.back())
+        pattern64.pop_back();
+
+    if (pattern64.empty())
+        return Geometry{};
+
+    Clipper2Lib::Paths64 all;
+    for (auto const &path : geo.paths)
+    {
+        if (path.points.empty())
+            continue;
+
+        bool closed = path.points.size() > 1 && path.points[0] == path.points.back();
+        Clipper2Lib::Path64 path64 = to_path64(path, precision);
+        if (closed)
+            path64.pop_back();
+
+        Clipper2Lib::Paths64 paths = diff
+                                         ? Clipper2Lib::MinkowskiDiff(pattern64, path64, closed)
+                                         : Clipper2Lib::MinkowskiSum(pattern64, path64, closed);
+        all.insert(all.end(), paths.begin(), paths.end());
+    }
+
+    Clipper2Lib::Paths64 merged = Clipper2Lib::Union(all, Clipper2Lib::FillRule::NonZero);
+
+    Geometry out;
+    out.paths.reserve(merged.size());
+    for (auto const &p : merged)
+        out.paths.push_back(to_path(p, precision, true));
+
+    return out;
+}
+
+Geometry minkowski_sum(Path const &pattern, Geometry const &geo)
+{
+    return minkowski(pattern, geo, false);
+}
+
+Geometry minkowski_diff(Path const &pattern, Geometry const &geo)
+{
+    return minkowski(pattern, geo, true);
+}
@@ -0,0 +1,393 @@
+use crate::{Geometry, Path, Polygon, V};
+
+/// The direction in which a closed ring winds in a coordinate system where y
+/// points up.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum Orientation {
+    Cw,
+    Ccw,
+}
+
+/// The area, centroid and second moments of area of a shape.
+///
+/// The second moments are taken with respect to the centroid, that is `xx` is
+/// the integral of `(x - cx)²`, `yy` the integral of `(y - cy)²` and `xy` the
+/// integral of `(x - cx) * (y - cy)` over the area of the shape.
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub struct Moments {
+    pub area: f64,
+    pub centroid: V,
+    pub xx: f64,
+    pub yy: f64,
+    pub xy: f64,
+}
+
+/// The principal axes of a shape, that is the orthogonal directions along
+/// which the shape is the most and the least spread out.
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub struct PrincipalAxes {
+    pub centroid: V,
+
+    /// The angle in radians of the major axis with respect to the x axis, in
+    /// `-PI/2..=PI/2`.
+    pub angle: f64,
+
+    /// The second moment of area along the major axis.
+    pub major: f64,
+
+    /// The second moment of area along the minor axis.
+    pub minor: f64,
+}
+
+impl Moments {
+    /// Return the principal axes of the shape which are the eigenvectors of
+    /// the matrix of the second moments.
+    pub fn principal_axes(&self) -> PrincipalAxes {
+        let mid = (self.xx + self.yy) / 2.0;
+        let r = f64::hypot((self.xx - self.yy) / 2.0, self.xy);
+
+        PrincipalAxes {
+            centroid: self.centroid,
+            angle: 0.5 * f64::atan2(2.0 * self.xy, self.xx - self.yy),
+            major: mid + r,
+            minor: mid - r,
+        }
+    }
+
+    fn from_rings<'a>(rings: impl IntoIterator<Item = (&'a Path, f64)>) -> Option<Self> {
+        let mut rings = rings.into_iter().peekable();
+
+        // integrate relative to a point on the shape to keep the error low
+        // when the shape is far from the origin
+        let o = rings.peek()?.0.first()?;
+
+        let (mut a, mut sx, mut sy, mut sxx, mut syy, mut sxy) = (0.0, 0.0, 0.0, 0.0, 0.0, 0.0);
+        for (ring, sign) in rings {
+            for (p0, p1) in ring.closed_segments() {
+                let (p0, p1) = (p0 - o, p1 - o);
+                let c = sign * (p0.x * p1.y - p1.x * p0.y);
+
+                a += c;
+                sx += c * (p0.x + p1.x);
+                sy += c * (p0.y + p1.y);
+                sxx += c * (p0.x * p0.x + p0.x * p1.x + p1.x * p1.x);
+                syy += c * (p0.y * p0.y + p0.y * p1.y + p1.y * p1.y);
+                sxy += c * (2.0 * p0.x * p0.y + p0.x * p1.y + p1.x * p0.y + 2.0 * p1.x * p1.y);
+            }
+        }
+
+        let area = a / 2.0;
+        if area.abs() < crate::PRECISION_2 {
+            return None;
+        }
+
+        let (cx, cy) = (sx / (6.0 * area), sy / (6.0 * area));
+        Some(Self {
+            area,
+            centroid: o + V::new(cx, cy),
+            xx: sxx / 12.0 - area * cx * cx,
+            yy: syy / 12.0 - area * cy * cy,
+            xy: sxy / 24.0 - area * cx * cy,
+        })
+    }
+
+    /// Flip the signs of the moments if needed so that the area is positive.
+    fn normalized(mut self) -> Self {
+        if self.area < 0.0 {
+            self.area = -self.area;
+            self.xx = -self.xx;
+            self.yy = -self.yy;
+            self.xy = -self.xy;
+        }
+        self
+    }
+}
+
+impl PrincipalAxes {
+    /// Return the unit vector along the major axis.
+    pub fn major_axis(&self) -> V {
+        V::polar(self.angle, 1.0)
+    }
+
+    /// Return the unit vector along the minor axis.
+    pub fn minor_axis(&self) -> V {
+        let d = self.major_axis();
+        V::new(-d.y, d.x)
+    }
+}
+
+impl Path {
+    /// Return the Orientation of the Path considered as implicitly closed.
+    ///
+    /// Degenerate paths that do not enclose any area are considered
+    /// counter-clockwise.
+    ///
+    /// ```rust
+    /// # use lart::*;
+    /// let mut p = polygon!(v(0, 0), v(10, 0), v(10, 10));
+    /// assert_eq!(p.orientation(), Orientation::Ccw);
+    /// p.reverse();
+    /// assert_eq!(p.orientation(), Orientation::Cw);
+    /// p.make_ccw();
+    /// assert_eq!(p, polygon!(v(0, 0), v(10, 0), v(10, 10)));
+    /// ```
+    pub fn orientation(&self) -> Orientation {
+        if self.signed_area() < 0.0 {
+            Orientation::Cw
+        } else {
+            Orientation::Ccw
+        }
+    }
+
+    /// Reverse the Path if needed so that it winds counter-clockwise, like
+    /// the exterior of a Polygon.
+    pub fn make_ccw(&mut self) {
+        if self.orientation() == Orientation::Cw {
+            self.reverse();
+        }
+    }
+
+    /// Reverse the Path if needed so that it winds clockwise, like the holes
+    /// of a Polygon.
+    pub fn make_cw(&mut self) {
+        if self.orientation() == Orientation::Ccw {
+            self.reverse();
+        }
+    }
+
+    /// Return the center of mass of the area enclosed by the Path considered
+    /// as implicitly closed.
+    ///
+    /// Paths that do not enclose any area fall back to `Path::centroid`.
+    ///
+    /// ```rust
+    /// # use lart::*;
+    /// // most of the vertices are on the right, but most of the area is not
+    /// let p = polygon!(v(0, 0), v(9, 0), v(10, 0), v(10, 1), v(10, 2), v(0, 2));
+    /// assert!(p.polygon_centroid().almost_equal(v(5, 1)));
+    /// assert!(p.centroid().x > 6.0);
+    /// ```
+    pub fn polygon_centroid(&self) -> V {
+        self.moments()
+            .map_or_else(|| self.centroid(), |m| m.centroid)
+    }
+
+    /// Return the area, centroid and second moments of the area enclosed by
+    /// the Path considered as implicitly closed, regardless of its
+    /// orientation.
+    ///
+    /// Return None if the Path doesn't enclose any area.
+    ///
+    /// ```rust
+    /// # use lart::*;
+    /// let p = Path::from(Rect::with_dimensions(v(10, 10), 6.0, 2.0));
+    /// let m = p.moments().unwrap();
+    /// assert_eq!(m.area, 12.0);
+    /// assert!(m.centroid.almost_equal(v(13, 11)));
+    /// assert!((m.xx - 36.0).abs() < 1e-9);
+    /// assert!((m.yy - 4.0).abs() < 1e-9);
+    /// assert!(m.xy.abs() < 1e-9);
+    ///
+    /// assert!(path!(v(0, 0), v(10, 10)).moments().is_none());
+    /// ```
+    pub fn moments(&self) -> Option<Moments> {
+        Moments::from_rings([(self, 1.0)]).map(Moments::normalized)
+    }
+
+    /// Return the principal axes of the area enclosed by the Path.
+    ///
+    /// ```rust
+    /// # use lart::*;
+    /// let mut p = Path::from(Rect::with_dimensions(v(-3, -1), 6.0, 2.0));
+    /// p *= &Xform::rot(PI / 6.0);
+    ///
+    /// let axes = p.principal_axes().unwrap();
+    /// assert!(axes.centroid.almost_equal(v(0, 0)));
+    /// assert!((axes.angle - PI / 6.0).abs() < 1e-9);
+    /// assert!(axes.major > axes.minor);
+    /// ```
+    pub fn principal_axes(&self) -> Option<PrincipalAxes> {
+        self.moments().map(|m| m.principal_axes())
+    }
+
+    /// Return whether the Path considered as implicitly closed is a convex
+    /// polygon, collinear and repeated vertices are allowed.
+    ///
+    /// ```rust
+    /// # use lart::*;
+    /// assert!(polygon!(v(0, 0), v(5, 0), v(10, 0), v(10, 10), v(0, 10)).is_convex());
+    /// assert!(!polygon!(v(0, 0), v(10, 0), v(5, 2), v(10, 10), v(0, 10)).is_convex());
+    ///
+    /// // a pentagram turns always in the same direction, but it's not convex
+    /// let star = (0..5).map(|i| V::polar(f64::from(i) * 4.0 * PI / 5.0, 10.0)).collect::<Path>();
+    /// assert!(!star.is_convex());
+    /// ```
+    pub fn is_convex(&self) -> bool {
+        let mut pts = self.points.clone();
+        pts.dedup();
+        while pts.len() > 1 && pts.first() == pts.last() {
+            pts.pop();
+        }
+
+        if pts.len() < 3 {
+            return false;
+        }
+
+        let n = pts.len();
+        let (mut sign, mut turning) = (0.0, 0.0);
+        for i in 0..n {
+            let (a, b, c) = (pts[i], pts[(i + 1) % n], pts[(i + 2) % n]);
+            let (d0, d1) = (b - a, c - b);
+            let cross = d0.x * d1.y - d0.y * d1.x;
+
+            if cross != 0.0 {
+                if cross * sign < 0.0 {
+                    return false;
+                }
+                sign = cross;
+            }
+
+            turning += f64::atan2(cross, d0.dot(d1));
+        }
+
+        // the vertices of a convex polygon turn around only once
+        sign != 0.0 && (turning.abs() - std::f64::consts::TAU).abs() < 1e-6
+    }
+
+    /// Return whether the Path never crosses nor touches itself.
+    ///
+    /// ```rust
+    /// # use lart::*;
+    /// assert!(polygon!(v(0, 0), v(10, 0), v(10, 10), v(0, 10)).is_simple());
+    /// assert!(!polygon!(v(0, 0), v(10, 10), v(10, 0), v(0, 10)).is_simple());
+    /// ```
+    pub fn is_simple(&self) -> bool {
+        self.self_intersections().is_empty()
+    }
+}
+
+impl Polygon {
+    /// Return the signed area of the Polygon, that is the area of the
+    /// exterior minus the area of the holes with a positive sign if the
+    /// exterior winds counter-clockwise.
+    pub fn signed_area(&self) -> f64 {
+        let a = self.area();
+        match self.exterior.orientation() {
+            Orientation::Ccw => a,
+            Orientation::Cw => -a,
+        }
+    }
+
+    /// Return the center of mass of the Polygon taking its holes into
+    /// account.
+    ///
+    /// ```rust
+    /// # use lart::*;
+    /// let mut hole = Path::from(Rect::with_dimensions(v(5, 0), 5.0, 10.0));
+    /// hole.make_cw();
+    /// let p = Polygon::new(Path::from(Rect::with_dimensions(v(0, 0), 20.0, 10.0)), vec![hole]);
+    /// assert!(p.centroid().almost_equal(v(65.0 / 6.0, 5.0)));
+    /// ```
+    pub fn centroid(&self) -> V {
+        self.moments()
+            .map_or_else(|| self.exterior.centroid(), |m| m.centroid)
+    }
+
+    /// Return the area, centroid and second moments of the Polygon taking
+    /// its holes into account regardless of the orientation of its rings.
+    pub fn moments(&self) -> Option<Moments> {
+        let rings = self.paths().enumerate().map(|(i, r)| {
+            let sign = r.signed_area().signum();
+            (r, if i == 0 { sign } else { -sign })
+        });
+        Moments::from_rings(rings)
+    }
+
+    /// Return the principal axes of the Polygon taking its holes into
+    /// account.
+    pub fn principal_axes(&self) -> Option<PrincipalAxes> {
+        self.moments().map(|m| m.principal_axes())
+    }
+
+    /// Return whether none of the rings of the Polygon crosses or touches
+    /// itself or any other ring.
+    pub fn is_simple(&self) -> bool {
+        Geometry::from(self.clone()).is_simple()
+    }
+}
+
+impl Geometry {
+    /// Return the sum of the signed areas of the closed paths of this
+    /// Geometry.
+    ///
+    /// Holes must wind in the opposite direction of the paths that contain
+    /// them, like in the output of the boolean operations, for their area to
+    /// be subtracted.
+    ///
+    /// ```rust
+    /// # use lart::*;
+    /// let mut hole = Rect::with_dimensions(v(2, 2), 6.0, 6.0).closed_path();
+    /// hole.make_cw();
+    /// let g = Geometry::from_paths(vec![
+    ///     Rect::with_dimensions(v(0, 0), 10.0, 10.0).closed_path(),
+    ///     hole,
+    /// ]);
+    /// assert_eq!(g.signed_area(), 64.0);
+    /// assert_eq!(g.area(), 64.0);
+    /// ```
+    pub fn signed_area(&self) -> f64 {
+        self.closed_paths().map(Path::signed_area).sum()
+    }
+
+    /// Return the absolute value of the signed area of the Geometry.
+    pub fn area(&self) -> f64 {
+        self.signed_area().abs()
+    }
+
+    /// Return the center of mass of the area delimited by the closed paths of
+    /// this Geometry.
+    ///
+    /// Like in `Geometry::signed_area` holes must wind in the opposite
+    /// direction of the paths that contain them. Geometries that do not
+    /// enclose any area fall back to the average of all their vertices.
+    ///
+    /// ```rust
+    /// # use lart::*;
+    /// let g = Geometry::from_paths(vec![
+    ///     Rect::with_dimensions(v(0, 0), 2.0, 2.0).closed_path(),
+    ///     Rect::with_dimensions(v(10, 0), 4.0, 4.0).closed_path(),
+    /// ]);
+    /// assert!(g.centroid().almost_equal(v(9.8, 1.8)));
+    /// ```
+    pub fn centroid(&self) -> V {
+        self.moments().map_or_else(
+            || Path::from_iter(self.paths.iter().flat_map(Path::iter)).centroid(),
+            |m| m.centroid,
+        )
+    }
+
+    /// Return the area, centroid and second moments of the area delimited by
+    /// the closed paths of this Geometry.
+    ///
+    /// Like in `Geometry::signed_area` holes must wind in the opposite
+    /// direction of the paths that contain them.
+    pub fn moments(&self) -> Option<Moments> {
+        Moments::from_rings(self.closed_paths().map(|p| (p, 1.0))).map(Moments::normalized)
+    }
+
+    /// Return the principal axes of the area delimited by the closed paths
+    /// of this Geometry.
+    pub fn principal_axes(&self) -> Option<PrincipalAxes> {
+        self.moments().map(|m| m.principal_axes())
+    }
+
+    /// Return whether none of the paths of this Geometry crosses or touches
+    /// itself or any other path.
+    pub fn is_simple(&self) -> bool {
+        self.intersections().is_empty()
+    }
+
+    fn closed_paths(&self) -> impl Iterator<Item = &Path> + '_ {
+        self.paths.iter().filter(|p| p.is_closed())
+    }
+}
@@ -1,18 +1,35 @@
+pub mod arclength;
+pub mod bezier;
 pub mod bool_ops;
 pub mod containers;
+pub mod distance;
+pub mod ellipse;
 pub mod geometry;
+pub mod intersections;
+pub mod measures;
 pub mod path;
+pub mod path_builder;
+pub mod polygon;
 pub mod rect;
 pub(crate) mod types;
 pub mod utils;
 pub mod v;
+pub mod warp;
 pub mod xform;
 
+pub use arclength::ArcLengthIndex;
+pub use bezier::*;
+pub use bool_ops::{BoolOp, BoolOperand};
 pub use containers::*;
+pub use ellipse::Ellipse;
+pub use geometry::PointLocation;
+pub use intersections::{Intersection, SegmentRef};
+pub use measures::{Moments, Orientation, PrincipalAxes};
+pub use path_builder::PathBuilder;
 pub use rect::*;
 pub use types::*;
 pub use utils::*;
-pub use xform::Xform;
+pub use xform::{Homography, Xform, XformDecomposition};
 
 pub const PRECISION: f64 = 1e-6;
 pub const PRECISION_2: f64 = PRECISION * PRECISION;
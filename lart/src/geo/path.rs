@@ -1,6 +1,6 @@
 use std::ops::{Index, IndexMut, RangeBounds};
 
-use crate::{bbox_union, path, polar_angles, sample_seg, v, Bbox, Path, Rect, V};
+use crate::{bbox_union, path, polar_angles, sample_seg, v, Bbox, Path, Rect, Region, V};
 
 impl Path {
     pub const fn new() -> Self {
@@ -98,12 +98,28 @@ impl Path {
         self.segments().map(|(a, b)| a.dist2(b)).sum()
     }
 
+    /// Return the average of the vertices of the Path, the closing point of a
+    /// closed Path is counted only once.
+    ///
+    /// See `Path::polygon_centroid` for the center of mass of the area
+    /// enclosed by the Path.
+    ///
+    /// ```rust
+    /// # use lart::*;
+    /// assert_eq!(polygon!(v(0, 0), v(4, 0), v(4, 4), v(0, 4)).centroid(), v(2, 2));
+    /// assert_eq!(path!(v(0, 0), v(4, 0), v(4, 4)).centroid(), v(8.0 / 3.0, 4.0 / 3.0));
+    /// ```
     pub fn centroid(&self) -> V {
+        let points = match self.points.split_last() {
+            Some((_, rest)) if self.points.len() > 1 && self.is_closed() => rest,
+            _ => &self.points,
+        };
+
         let mut c = v(0, 0);
-        for p in self.iter() {
+        for &p in points {
             c += p
         }
-        c / (self.points.len() as f64)
+        c / (points.len() as f64)
     }
 
     pub fn slice(&self, r: impl RangeBounds<usize>) -> Path {
@@ -128,11 +144,68 @@ impl Path {
         )
     }
 
-    pub fn area(&self) -> f64 {
-        self.sarea().abs()
+    /// Return the [winding number][0] of the given point with respect to
+    /// this Path considered as implicitly closed.
+    ///
+    /// The winding number is positive if the Path winds counter-clockwise
+    /// around the point (in a coordinate system where y points up) and
+    /// negative otherwise.
+    ///
+    /// [0]: https://en.wikipedia.org/wiki/Winding_number
+    ///
+    /// ```rust
+    /// # use lart::*;
+    /// let mut p = polygon!(v(0, 0), v(10, 0), v(10, 10), v(0, 10));
+    /// assert_eq!(p.winding_number(v(5, 5)), 1);
+    /// assert_eq!(p.winding_number(v(15, 5)), 0);
+    /// p.reverse();
+    /// assert_eq!(p.winding_number(v(5, 5)), -1);
+    /// ```
+    pub fn winding_number(&self, p: V) -> i32 {
+        let mut wn = 0;
+        for (a, b) in self.closed_segments() {
+            if a.y <= p.y {
+                if b.y > p.y && p.orient(a, b) > 0.0 {
+                    wn += 1;
+                }
+            } else if b.y <= p.y && p.orient(a, b) < 0.0 {
+                wn -= 1;
+            }
+        }
+        wn
+    }
+
+    /// Return whether the given point is inside this Path considered as
+    /// implicitly closed, that is whether its winding number is not zero.
+    ///
+    /// ```rust
+    /// # use lart::*;
+    /// let p = path!(v(0, 0), v(10, 0), v(5, 10));
+    /// assert!(p.contains(v(5, 5)));
+    /// assert!(!p.contains(v(0, 10)));
+    /// ```
+    pub fn contains(&self, p: V) -> bool {
+        self.winding_number(p) != 0
     }
 
-    fn sarea(&self) -> f64 {
+    pub fn area(&self) -> f64 {
+        self.signed_area().abs()
+    }
+
+    /// Return the signed area enclosed by the Path considered as implicitly
+    /// closed.
+    ///
+    /// The area is positive if the Path winds counter-clockwise in a
+    /// coordinate system where y points up and negative otherwise.
+    ///
+    /// ```rust
+    /// # use lart::*;
+    /// let mut p = polygon!(v(0, 0), v(10, 0), v(10, 10), v(0, 10));
+    /// assert_eq!(p.signed_area(), 100.0);
+    /// p.reverse();
+    /// assert_eq!(p.signed_area(), -100.0);
+    /// ```
+    pub fn signed_area(&self) -> f64 {
         if self.points.len() < 3 {
             return 0.0;
         }
@@ -153,6 +226,12 @@ impl Bbox for Path {
     }
 }
 
+impl Region for Path {
+    fn contains(&self, p: V) -> bool {
+        Path::contains(self, p)
+    }
+}
+
 impl Index<usize> for Path {
     type Output = V;
 
@@ -0,0 +1,193 @@
+use std::f64::consts::TAU;
+
+use crate::{v, CubicBezier, Ellipse, Geometry, Path, QuadBezier, V};
+
+/// A PathBuilder creates a Geometry out of a sequence of drawing commands like
+/// lines, Bezier curves and elliptical arcs, very much like SVG paths.
+///
+/// Curves are flattened so that the final polylines never deviate from the
+/// true curves more than the given tolerance which is expressed in the same
+/// units as the coordinates, usually mm.
+///
+/// ```rust
+/// # use lart::*;
+/// let g = PathBuilder::new(0.01)
+///     .move_to(v(0, 0))
+///     .line_to(v(10, 0))
+///     .quad_to(v(10, 10), v(0, 10))
+///     .close()
+///     .move_to(v(20, 0))
+///     .arc_to(v(5, 5), 0.0, false, true, v(30, 0))
+///     .build();
+///
+/// assert_eq!(g.paths().len(), 2);
+/// assert!(g.paths()[0].is_closed());
+/// assert_eq!(g.paths()[1].first(), Some(v(20, 0)));
+/// assert_eq!(g.paths()[1].last(), Some(v(30, 0)));
+/// assert!(g.paths()[1].iter().all(|p| (p.dist(v(25, 0)) - 5.0).abs() < 1e-9));
+/// ```
+#[derive(Debug, Clone)]
+pub struct PathBuilder {
+    tolerance: f64,
+    geo: Geometry,
+    cur: Path,
+    start: V,
+    pos: V,
+}
+
+impl PathBuilder {
+    /// Create a new PathBuilder that flattens curves with the given maximum
+    /// deviation.
+    ///
+    /// The current point starts at the origin.
+    pub fn new(tolerance: f64) -> Self {
+        Self {
+            tolerance,
+            geo: Geometry::new(),
+            cur: Path::new(),
+            start: v(0, 0),
+            pos: v(0, 0),
+        }
+    }
+
+    /// Start a new sub path at the given point.
+    pub fn move_to(&mut self, p: V) -> &mut Self {
+        self.flush();
+        self.start = p;
+        self.pos = p;
+        self
+    }
+
+    /// Add a straight line from the current point to the given one.
+    pub fn line_to(&mut self, p: V) -> &mut Self {
+        self.begin();
+        self.cur.push(p);
+        self.pos = p;
+        self
+    }
+
+    /// Add a quadratic Bezier curve from the current point to `p` using `c`
+    /// as the control point.
+    pub fn quad_to(&mut self, c: V, p: V) -> &mut Self {
+        self.begin();
+        QuadBezier::new(self.pos, c, p).flatten_into(&mut self.cur, self.tolerance);
+        self.pos = p;
+        self
+    }
+
+    /// Add a cubic Bezier curve from the current point to `p` using `c1` and
+    /// `c2` as the control points.
+    pub fn cubic_to(&mut self, c1: V, c2: V, p: V) -> &mut Self {
+        self.begin();
+        CubicBezier::new(self.pos, c1, c2, p).flatten_into(&mut self.cur, self.tolerance);
+        self.pos = p;
+        self
+    }
+
+    /// Add an elliptical arc from the current point to `p` following the
+    /// [SVG arc semantics][0].
+    ///
+    /// The ellipse has the radii `r.x` and `r.y` and it's rotated by
+    /// `rotation` radians. Out of the four candidate arcs `large_arc` selects
+    /// whether to take one spanning more than half a turn while `sweep`
+    /// selects whether the angle increases along the arc.
+    ///
+    /// If the radii are too small to reach `p` they're scaled up uniformly,
+    /// if either of them is zero a straight line is added instead.
+    ///
+    /// [0]: https://www.w3.org/TR/SVG/implnote.html#ArcImplementationNotes
+    pub fn arc_to(&mut self, r: V, rotation: f64, large_arc: bool, sweep: bool, p: V) -> &mut Self {
+        let p0 = self.pos;
+        if p0 == p {
+            return self;
+        }
+
+        let (mut rx, mut ry) = (r.x.abs(), r.y.abs());
+        if rx == 0.0 || ry == 0.0 {
+            return self.line_to(p);
+        }
+
+        // convert the endpoint parameterization to the center one, see
+        // https://www.w3.org/TR/SVG/implnote.html#ArcConversionEndpointToCenter
+        let (sin_phi, cos_phi) = rotation.sin_cos();
+        let d = (p0 - p) / 2.0;
+        let x1 = cos_phi * d.x + sin_phi * d.y;
+        let y1 = -sin_phi * d.x + cos_phi * d.y;
+
+        let lambda = (x1 / rx).powi(2) + (y1 / ry).powi(2);
+        if lambda > 1.0 {
+            rx *= lambda.sqrt();
+            ry *= lambda.sqrt();
+        }
+
+        let num = (rx * ry).powi(2) - (rx * y1).powi(2) - (ry * x1).powi(2);
+        let den = (rx * y1).powi(2) + (ry * x1).powi(2);
+        let mut coef = f64::max(0.0, num / den).sqrt();
+        if large_arc == sweep {
+            coef = -coef;
+        }
+
+        let cx1 = coef * rx * y1 / ry;
+        let cy1 = -coef * ry * x1 / rx;
+        let m = (p0 + p) / 2.0;
+        let c = v(
+            cos_phi * cx1 - sin_phi * cy1 + m.x,
+            sin_phi * cx1 + cos_phi * cy1 + m.y,
+        );
+
+        let theta0 = v((x1 - cx1) / rx, (y1 - cy1) / ry).angle();
+        let theta1 = v((-x1 - cx1) / rx, (-y1 - cy1) / ry).angle();
+        let mut dtheta = (theta1 - theta0) % TAU;
+        if sweep && dtheta < 0.0 {
+            dtheta += TAU;
+        } else if !sweep && dtheta > 0.0 {
+            dtheta -= TAU;
+        }
+
+        self.begin();
+        Ellipse::new(c, rx, ry, rotation).arc_into(
+            &mut self.cur,
+            theta0,
+            theta0 + dtheta,
+            self.tolerance,
+        );
+        // make sure to end exactly where requested regardless of rounding
+        let last = self.cur.len() - 1;
+        self.cur[last] = p;
+        self.pos = p;
+
+        self
+    }
+
+    /// Close the current sub path by going back to its starting point.
+    ///
+    /// Drawing commands after a close start a new sub path from the same
+    /// starting point.
+    pub fn close(&mut self) -> &mut Self {
+        if !self.cur.is_empty() {
+            self.cur.close();
+        }
+        self.flush();
+        self.pos = self.start;
+        self
+    }
+
+    /// Return the Geometry built so far and reset the builder.
+    pub fn build(&mut self) -> Geometry {
+        self.flush();
+        std::mem::replace(&mut self.geo, Geometry::new())
+    }
+
+    fn begin(&mut self) {
+        if self.cur.is_empty() {
+            self.cur.push(self.pos);
+        }
+    }
+
+    fn flush(&mut self) {
+        let p = std::mem::replace(&mut self.cur, Path::new());
+        if p.len() > 1 {
+            self.geo.push_path(p);
+        }
+    }
+}
@@ -0,0 +1,77 @@
+use crate::{ffi, Bbox, BoolOperand, FillRule, Geometry, Path, Polygon, Rect};
+
+impl Polygon {
+    /// Create a new top level Polygon with the given exterior and holes.
+    pub fn new(exterior: Path, holes: Vec<Path>) -> Self {
+        Self {
+            exterior,
+            holes,
+            depth: 0,
+        }
+    }
+
+    /// Return an iterator over all the rings of the Polygon, the exterior
+    /// first and then the holes.
+    pub fn paths(&self) -> impl Iterator<Item = &Path> + '_ {
+        std::iter::once(&self.exterior).chain(&self.holes)
+    }
+
+    /// Return the area of the Polygon that is the area of the exterior minus
+    /// the area of the holes.
+    pub fn area(&self) -> f64 {
+        self.exterior.area() - self.holes.iter().map(Path::area).sum::<f64>()
+    }
+}
+
+impl Geometry {
+    /// Return the Polygons that make up the closed paths of this Geometry,
+    /// open paths are ignored.
+    ///
+    /// Overlapping paths are merged together following the
+    /// `FillRule::NonZero` rule like in the boolean operations.
+    ///
+    /// ```rust
+    /// # use lart::*;
+    /// let outer = Rect::with_dimensions(v(0, 0), 10.0, 10.0);
+    /// let hole = Rect::with_dimensions(v(2, 2), 6.0, 6.0);
+    /// let island = Rect::with_dimensions(v(4, 4), 2.0, 2.0);
+    ///
+    /// let mut polys = ((outer - hole) | island).polygons();
+    /// polys.sort_by_key(|p| p.depth);
+    ///
+    /// assert_eq!(polys.len(), 2);
+    /// assert_eq!((polys[0].depth, polys[0].holes.len()), (0, 1));
+    /// assert!((polys[0].area() - 64.0).abs() < 1e-6);
+    /// assert_eq!((polys[1].depth, polys[1].holes.len()), (1, 0));
+    /// assert!((polys[1].area() - 4.0).abs() < 1e-6);
+    /// ```
+    pub fn polygons(&self) -> Vec<Polygon> {
+        self.polygons_with(FillRule::NonZero)
+    }
+
+    /// Return the Polygons that make up the closed paths of this Geometry
+    /// using the given FillRule to decide which regions are inside.
+    pub fn polygons_with(&self, fill_rule: FillRule) -> Vec<Polygon> {
+        ffi::polygons(self, fill_rule)
+    }
+}
+
+impl Bbox for Polygon {
+    fn bbox(&self) -> Option<Rect> {
+        self.exterior.bbox()
+    }
+}
+
+impl BoolOperand for Polygon {
+    fn for_each_path(&self, f: impl FnMut(&Path)) {
+        self.paths().for_each(f);
+    }
+}
+
+impl From<Polygon> for Geometry {
+    fn from(p: Polygon) -> Self {
+        let mut g = Geometry::from(p.exterior);
+        g.push_paths(p.holes);
+        g
+    }
+}
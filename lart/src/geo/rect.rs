@@ -6,6 +6,11 @@ pub trait Bbox {
     fn bbox(&self) -> Option<Rect>;
 }
 
+/// A Region is an area of the plane that can be filled with points.
+pub trait Region: Bbox {
+    fn contains(&self, p: V) -> bool;
+}
+
 #[derive(Debug, Clone, PartialEq)]
 pub struct Rect {
     min: V,
@@ -86,15 +91,39 @@ impl Rect {
         })
     }
 
+    /// Return the distance of the given point from the Rect, zero if the
+    /// point is inside.
+    ///
+    /// ```rust
+    /// # use lart::*;
+    /// let r = Rect::with_dimensions(v(0, 0), 10.0, 10.0);
+    /// assert_eq!(r.dist(v(5, 5)), 0.0);
+    /// assert_eq!(r.dist(v(15, 5)), 5.0);
+    /// assert_eq!(r.dist(v(13, 14)), 5.0);
+    /// ```
     pub fn dist(&self, v: V) -> f64 {
         self.dist2(v).sqrt()
     }
 
+    /// Return the squared distance of the given point from the Rect, zero if
+    /// the point is inside.
+    ///
+    /// ```rust
+    /// # use lart::*;
+    /// let r = Rect::with_dimensions(v(0, 0), 10.0, 10.0);
+    /// assert_eq!(r.dist2(v(5, 5)), 0.0);
+    ///
+    /// // the distances along the axes used to be multiplied, so the points
+    /// // beside the Rect were at distance zero too
+    /// assert_eq!(r.dist2(v(15, 5)), 25.0);
+    /// assert_eq!(r.dist2(v(5, -3)), 9.0);
+    /// assert_eq!(r.dist2(v(13, 14)), 25.0);
+    /// ```
     pub fn dist2(&self, v: V) -> f64 {
         let vx = f64::max(self.min.x - v.x, v.x - self.max.x);
         let vy = f64::max(self.min.y - v.y, v.y - self.max.y);
 
-        f64::max(vx, 0.0).powi(2) * f64::max(vy, 0.0).powi(2)
+        f64::max(vx, 0.0).powi(2) + f64::max(vy, 0.0).powi(2)
     }
 
     pub fn center(&self) -> V {
@@ -200,6 +229,12 @@ impl Bbox for Rect {
     }
 }
 
+impl Region for Rect {
+    fn contains(&self, p: V) -> bool {
+        Rect::contains(self, p)
+    }
+}
+
 impl<B: Bbox> Bbox for &B {
     fn bbox(&self) -> Option<Rect> {
         (*self).bbox()
@@ -26,7 +26,7 @@ pub mod ffi {
     /// assert_eq!(p.first(), Some(v(3,4)));
     /// assert_eq!(p.last(), Some(v(-2,2)));
     /// ```
-    #[derive(Clone, PartialEq)]
+    #[derive(Default, Clone, PartialEq)]
     pub struct Path {
         points: Vec<V>,
     }
@@ -46,11 +46,115 @@ pub mod ffi {
     /// doc.geometry(&g1 & &g2);
     /// doc.geometry(&g1 | &g2);
     /// ```
-    #[derive(Debug, Clone)]
+    #[derive(Default, Debug, Clone)]
     pub struct Geometry {
         paths: Vec<Path>,
     }
 
+    /// A Polygon is an area delimited by a closed exterior ring that can
+    /// contain holes.
+    ///
+    /// Polygons are usually extracted from a Geometry via `Geometry::polygons`
+    /// which also computes how deeply nested each Polygon is. The depth is
+    /// the number of Polygons that contain this one, that is a Polygon inside
+    /// the hole of a top level Polygon has a depth of 1.
+    ///
+    /// ```rust
+    /// # use lart::*;
+    /// let p = Polygon::new(
+    ///     Rect::with_dimensions(v(0, 0), 10.0, 10.0).closed_path(),
+    ///     vec![Rect::with_dimensions(v(2, 2), 6.0, 6.0).closed_path()],
+    /// );
+    /// assert_eq!(p.area(), 64.0);
+    /// assert_eq!(p.depth, 0);
+    /// ```
+    #[derive(Debug, Clone, PartialEq)]
+    pub struct Polygon {
+        /// The closed outer ring of the Polygon.
+        exterior: Path,
+        /// The closed rings of the holes inside the exterior.
+        holes: Vec<Path>,
+        /// How many Polygons contain this Polygon.
+        depth: usize,
+    }
+
+    /// How the corners of the paths are joined together when buffering a
+    /// Geometry.
+    #[derive(Debug)]
+    enum JoinType {
+        /// Corners are squared off at exactly the buffer distance.
+        Square,
+        /// Corners are cut off by a straight line.
+        Bevel,
+        /// Corners are rounded with an arc of the buffer distance.
+        Round,
+        /// Corners are extended until the edges meet unless that would exceed
+        /// the miter limit in which case they're squared off.
+        Miter,
+    }
+
+    /// How the ends of open paths are capped when buffering a Geometry.
+    ///
+    /// Closed paths are always buffered as polygons and this setting does not
+    /// apply to them.
+    #[derive(Debug)]
+    enum EndType {
+        /// Ends are cut squarely at the endpoints without any extension.
+        Butt,
+        /// Ends are squared off after extending them by the buffer distance.
+        Square,
+        /// Ends are rounded with a semicircle of the buffer distance.
+        Round,
+        /// Ends are joined together as if the path was closed, but the path
+        /// is treated as a line rather than a polygon.
+        Joined,
+    }
+
+    /// The options that drive how a Geometry is buffered.
+    ///
+    /// ```rust
+    /// # use lart::*;
+    /// let opts = BufferOptions::default()
+    ///     .with_join_type(JoinType::Miter)
+    ///     .with_miter_limit(4.0);
+    /// assert_eq!(opts.join_type, JoinType::Miter);
+    /// assert_eq!(opts.end_type, EndType::Round);
+    /// assert_eq!(opts.miter_limit, 4.0);
+    /// ```
+    #[derive(Debug, Clone)]
+    struct BufferOptions {
+        /// How to join the corners of the paths.
+        join_type: JoinType,
+        /// How to cap the ends of open paths, closed paths are always
+        /// buffered as polygons whatever the end type.
+        end_type: EndType,
+        /// The maximum distance, in multiples of the buffer delta, that
+        /// mitered corners can extend before being squared off.
+        miter_limit: f64,
+        /// The maximum distance the flattened round joins and ends are allowed
+        /// to deviate from the true arc. If zero a sensible default is chosen
+        /// based on the buffer delta.
+        arc_tolerance: f64,
+    }
+
+    /// The rule that decides which regions are considered inside a set of
+    /// possibly overlapping or self intersecting paths in boolean operations.
+    ///
+    /// The winding number of a point is the number of times the paths wind
+    /// around it, counting clockwise and counter-clockwise windings with
+    /// opposite signs.
+    #[derive(Debug)]
+    enum FillRule {
+        /// A region is inside if its winding number is odd.
+        EvenOdd,
+        /// A region is inside if its winding number is not zero.
+        NonZero,
+        /// A region is inside if its winding number is positive.
+        Positive,
+        /// A region is inside if its winding number is negative.
+        Negative,
+    }
+
     unsafe extern "C++" {
         include!("lart/include/lart.h");
 
@@ -59,13 +163,19 @@ pub mod ffi {
         fn new_clipper() -> UniquePtr<Clipper>;
         fn add_subject(self: Pin<&mut Clipper>, polygon: &Path);
         fn add_clip(self: Pin<&mut Clipper>, polygon: &Path);
+        fn set_fill_rule(self: Pin<&mut Clipper>, fill_rule: FillRule);
 
         fn union_(self: Pin<&mut Clipper>) -> Geometry;
         fn intersection(self: Pin<&mut Clipper>) -> Geometry;
         fn difference(self: Pin<&mut Clipper>) -> Geometry;
         fn symmetric_difference(self: Pin<&mut Clipper>) -> Geometry;
 
-        fn buffer(geo: &Geometry, delta: f64) -> Geometry;
+        fn buffer(geo: &Geometry, delta: f64, opts: &BufferOptions) -> Geometry;
+
+        fn polygons(geo: &Geometry, fill_rule: FillRule) -> Vec<Polygon>;
+
+        fn minkowski_sum(pattern: &Path, geo: &Geometry) -> Geometry;
+        fn minkowski_diff(pattern: &Path, geo: &Geometry) -> Geometry;
     }
 }
 
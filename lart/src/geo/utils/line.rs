@@ -87,3 +87,34 @@ pub fn line_x_line_t((a, b): (V, V), (c, d): (V, V)) -> Option<f64> {
     let num = (a.x - c.x) * (c.y - d.y) - (a.y - c.y) * (c.x - d.x);
     Some(num / det)
 }
+
+/// Find the point on a segment that is closest to the given point returning
+/// its position.
+///
+/// ```rust
+/// # use lart::*;
+/// assert_eq!(seg_closest_point((v(0,0), v(10,0)), v(4,3)), v(4,0));
+/// assert_eq!(seg_closest_point((v(0,0), v(10,0)), v(-4,3)), v(0,0));
+/// ```
+pub fn seg_closest_point(seg: (V, V), p: V) -> V {
+    linterp(seg.0, seg.1, seg_closest_point_t(seg, p))
+}
+
+/// Find the point on a segment that is closest to the given point returning
+/// its parameter along the segment.
+///
+/// ```rust
+/// # use lart::*;
+/// assert_eq!(seg_closest_point_t((v(0,0), v(10,0)), v(4,3)), 0.4);
+/// assert_eq!(seg_closest_point_t((v(0,0), v(10,0)), v(14,3)), 1.0);
+/// assert_eq!(seg_closest_point_t((v(1,1), v(1,1)), v(14,3)), 0.0);
+/// ```
+pub fn seg_closest_point_t((a, b): (V, V), p: V) -> f64 {
+    let d = b - a;
+    let l2 = d.norm2();
+    if l2 == 0.0 {
+        return 0.0;
+    }
+
+    ((p - a).dot(d) / l2).clamp(0.0, 1.0)
+}
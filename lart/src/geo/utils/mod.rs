@@ -1,5 +1,7 @@
 use std::ops::{Add, Mul, Sub};
 
+use crate::{v, V};
+
 mod fkey;
 mod frange;
 mod line;
@@ -54,3 +56,22 @@ pub fn map(
 ) -> f64 {
     linterp(ostart.into(), oend.into(), mapu(v, start, end))
 }
+
+/// Return the center of the circle passing through the three given points,
+/// if they're not collinear.
+///
+/// ```rust
+/// # use lart::*;
+/// assert_eq!(circumcenter(v(0, 0), v(4, 0), v(0, 2)), Some(v(2, 1)));
+/// assert_eq!(circumcenter(v(0, 0), v(1, 1), v(2, 2)), None);
+/// ```
+pub fn circumcenter(a: V, b: V, c: V) -> Option<V> {
+    let (b, c) = (b - a, c - a);
+    let d = 2.0 * (b.x * c.y - b.y * c.x);
+    if d == 0.0 {
+        return None;
+    }
+
+    let (b2, c2) = (b.norm2(), c.norm2());
+    Some(a + v(c.y * b2 - b.y * c2, b.x * c2 - c.x * b2) / d)
+}
@@ -0,0 +1,73 @@
+use crate::{Geometry, Path, V};
+
+/// The maximum number of times a segment is bisected while warping it, it only
+/// matters for maps with discontinuities or singularities.
+const MAX_WARP_DEPTH: u32 = 16;
+
+impl Geometry {
+    /// Transform all the points of the Geometry with the given arbitrary map.
+    ///
+    /// Since straight segments in general do not stay straight under non
+    /// linear maps, each segment is bisected until its warped version is at
+    /// most `max_seg_len` long.
+    ///
+    /// ```rust
+    /// # use lart::*;
+    /// let g = Geometry::from(path!(v(-10, 0), v(10, 0)));
+    /// let bent = g.warp(|p| v(p.x, p.x * p.x / 10.0), 1.0);
+    ///
+    /// let p = &bent.paths()[0];
+    /// assert_eq!(p.first(), Some(v(-10, 10)));
+    /// assert_eq!(p.last(), Some(v(10, 10)));
+    /// assert!(p.segments().all(|(a, b)| a.dist(b) <= 1.0));
+    /// assert!(p.iter().all(|pt| (pt.y - pt.x * pt.x / 10.0).abs() < 1e-9));
+    /// ```
+    pub fn warp(&self, f: impl Fn(V) -> V, max_seg_len: f64) -> Self {
+        Self::from_paths(self.paths.iter().map(|p| p.warp(&f, max_seg_len)).collect())
+    }
+}
+
+impl Path {
+    /// Transform all the points of the Path with the given arbitrary map,
+    /// bisecting each segment until its warped version is at most
+    /// `max_seg_len` long.
+    ///
+    /// See `Geometry::warp`.
+    pub fn warp(&self, f: impl Fn(V) -> V, max_seg_len: f64) -> Self {
+        let mut out = Path::with_capacity(self.len());
+
+        let mut points = self.iter();
+        let Some(mut a) = points.next() else {
+            return out;
+        };
+        let mut fa = f(a);
+        out.push(fa);
+
+        for b in points {
+            let fb = f(b);
+            warp_segment(&mut out, &f, (a, fa), (b, fb), max_seg_len, MAX_WARP_DEPTH);
+            (a, fa) = (b, fb);
+        }
+
+        out
+    }
+}
+
+fn warp_segment(
+    out: &mut Path,
+    f: &impl Fn(V) -> V,
+    (a, fa): (V, V),
+    (b, fb): (V, V),
+    max_seg_len: f64,
+    depth: u32,
+) {
+    if depth == 0 || fa.dist2(fb) <= max_seg_len * max_seg_len {
+        out.push(fb);
+        return;
+    }
+
+    let m = (a + b) / 2.0;
+    let fm = f(m);
+    warp_segment(out, f, (a, fa), (m, fm), max_seg_len, depth - 1);
+    warp_segment(out, f, (m, fm), (b, fb), max_seg_len, depth - 1);
+}
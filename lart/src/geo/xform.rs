@@ -1,6 +1,6 @@
 use std::ops::{Mul, MulAssign};
 
-use crate::{v, Geometry, Path, Rect, V};
+use crate::{v, Ellipse, Geometry, Path, Rect, PRECISION_2, V};
 
 /// An Xform is 2D transformation to scale, rotate, mirror, translate, etc... a
 /// V, Path and Geometry.
@@ -146,6 +146,253 @@ impl Xform {
             * Self::scale(v(dst.width() / src.width(), dst.height() / src.height()))
             * Self::xlate(dst.center())
     }
+
+    /// Create an Xform that skews the input geometry by the given angles in
+    /// radians, `ax` along the x axis and `ay` along the y axis.
+    ///
+    /// ```rust
+    /// # use lart::*;
+    /// let p = v(0, 10) * Xform::skew(TAU / 8.0, 0.0);
+    /// assert!(p.almost_equal(v(10, 10)));
+    /// ```
+    pub fn skew(ax: f64, ay: f64) -> Self {
+        Self {
+            a: v(1.0, ay.tan()),
+            b: v(ax.tan(), 1.0),
+            c: v(0.0, 0.0),
+        }
+    }
+
+    /// Create an Xform that mirrors the input geometry across the infinite
+    /// line passing through the two given points.
+    ///
+    /// ```rust
+    /// # use lart::*;
+    /// let m = Xform::mirror(v(0, 0), v(10, 10));
+    /// assert!((v(5, 0) * &m).almost_equal(v(0, 5)));
+    ///
+    /// let m = Xform::mirror(v(2, 0), v(2, 10));
+    /// assert!((v(0, 3) * &m).almost_equal(v(4, 3)));
+    /// ```
+    pub fn mirror(p0: V, p1: V) -> Self {
+        let d = (p1 - p0).normalized();
+        let (xx, yy, xy) = (d.x * d.x, d.y * d.y, d.x * d.y);
+        let m = Self {
+            a: v(xx - yy, 2.0 * xy),
+            b: v(2.0 * xy, yy - xx),
+            c: v(0.0, 0.0),
+        };
+
+        Self::xlate(-p0) * m * Self::xlate(p0)
+    }
+
+    /// Return the Xform that undoes this one, if the Xform is invertible.
+    ///
+    /// ```rust
+    /// # use lart::*;
+    /// let x = Xform::rot(1.0) * Xform::scale(v(2, 3)) * Xform::xlate(v(5, -2));
+    /// let p = v(4, 2) * &x * x.inverse().unwrap();
+    /// assert!(p.almost_equal(v(4, 2)));
+    ///
+    /// assert!(Xform::scale(v(0, 1)).inverse().is_none());
+    /// ```
+    pub fn inverse(&self) -> Option<Self> {
+        let det = self.a.x * self.b.y - self.b.x * self.a.y;
+        if det.abs() < PRECISION_2 {
+            return None;
+        }
+
+        let a = v(self.b.y, -self.a.y) / det;
+        let b = v(-self.b.x, self.a.x) / det;
+        let c = -(self.c.x * a + self.c.y * b);
+        Some(Self { a, b, c })
+    }
+
+    /// Decompose the Xform into a scale, followed by a skew along the x axis,
+    /// followed by a rotation and finally by a translation.
+    ///
+    /// Mirroring is represented by a negative y scale.
+    ///
+    /// ```rust
+    /// # use lart::*;
+    /// let x = Xform::scale(v(2, 3)) * Xform::rot(PI / 3.0) * Xform::xlate(v(5, -2));
+    /// let d = x.decompose();
+    /// assert!(d.translation.almost_equal(v(5, -2)));
+    /// assert!((d.rotation - PI / 3.0).abs() < 1e-9);
+    /// assert!(d.scale.almost_equal(v(2, 3)));
+    /// assert!(d.skew.abs() < 1e-9);
+    ///
+    /// let x = Xform::skew(0.5, 0.2) * Xform::mirror(v(0, 0), v(1, 3));
+    /// let y = x.decompose().xform();
+    /// assert!((v(3, 7) * &x).almost_equal(v(3, 7) * &y));
+    /// ```
+    pub fn decompose(&self) -> XformDecomposition {
+        let rotation = self.a.angle();
+        let (s, c) = rotation.sin_cos();
+
+        let sx = self.a.norm();
+        let sy = -s * self.b.x + c * self.b.y;
+        let k = if sy == 0.0 {
+            0.0
+        } else {
+            (c * self.b.x + s * self.b.y) / sy
+        };
+
+        XformDecomposition {
+            translation: self.c,
+            rotation,
+            scale: v(sx, sy),
+            skew: k.atan(),
+        }
+    }
+}
+
+/// The components of an Xform as returned by `Xform::decompose`.
+#[derive(Debug, Clone, PartialEq)]
+pub struct XformDecomposition {
+    pub translation: V,
+    /// The rotation angle in radians.
+    pub rotation: f64,
+    pub scale: V,
+    /// The skew angle in radians along the x axis.
+    pub skew: f64,
+}
+
+impl XformDecomposition {
+    /// Recompose the Xform.
+    pub fn xform(&self) -> Xform {
+        Xform::scale(self.scale)
+            * Xform::skew(self.skew, 0.0)
+            * Xform::rot(self.rotation)
+            * Xform::xlate(self.translation)
+    }
+}
+
+/// A Homography is a projective 2D transformation that maps straight lines to
+/// straight lines, but it doesn't preserve parallelism. It's the
+/// transformation that gives the perspective look.
+///
+/// Like Xform, Homography can be combined via the \* operator and `h1 * h2`
+/// applies `h1` first and then `h2`.
+///
+/// ```rust
+/// # use lart::*;
+/// let square = [v(0, 0), v(1, 0), v(1, 1), v(0, 1)];
+/// let trapezoid = [v(0, 0), v(10, 0), v(7, 5), v(3, 5)];
+/// let h = Homography::from_points(square, trapezoid).unwrap();
+///
+/// for (s, t) in square.iter().zip(&trapezoid) {
+///     assert!((*s * &h).almost_equal(*t));
+/// }
+///
+/// // the center of the square is pushed towards the far side
+/// let c = v(0.5, 0.5) * &h;
+/// assert!((c.x - 5.0).abs() < 1e-9);
+/// assert!(c.y > 2.5);
+/// ```
+#[derive(Debug, Clone, PartialEq)]
+pub struct Homography {
+    /// The row major 3x3 matrix of the transformation acting on column
+    /// vectors in homogeneous coordinates.
+    pub m: [[f64; 3]; 3],
+}
+
+impl Homography {
+    /// Create a new Homography that does not change the input entity.
+    pub fn identity() -> Self {
+        Self {
+            m: [[1.0, 0.0, 0.0], [0.0, 1.0, 0.0], [0.0, 0.0, 1.0]],
+        }
+    }
+
+    /// Create the Homography that maps each of the src points into the
+    /// corresponding dst point.
+    ///
+    /// Return None if no such transformation exists, for example if three of
+    /// the points are collinear.
+    pub fn from_points(src: [V; 4], dst: [V; 4]) -> Option<Self> {
+        // set m[2][2] to 1 and solve the 8x8 linear system for the remaining
+        // coefficients
+        let mut sys = [[0.0; 9]; 8];
+        for (i, (s, d)) in src.iter().zip(&dst).enumerate() {
+            sys[2 * i] = [s.x, s.y, 1.0, 0.0, 0.0, 0.0, -d.x * s.x, -d.x * s.y, d.x];
+            sys[2 * i + 1] = [0.0, 0.0, 0.0, s.x, s.y, 1.0, -d.y * s.x, -d.y * s.y, d.y];
+        }
+
+        for col in 0..8 {
+            let pivot =
+                (col..8).max_by(|&i, &j| sys[i][col].abs().total_cmp(&sys[j][col].abs()))?;
+            if sys[pivot][col].abs() < PRECISION_2 {
+                return None;
+            }
+            sys.swap(col, pivot);
+
+            for row in 0..8 {
+                if row == col {
+                    continue;
+                }
+
+                let pivot_row = sys[col];
+                let f = sys[row][col] / pivot_row[col];
+                for (x, p) in sys[row].iter_mut().zip(pivot_row).skip(col) {
+                    *x -= f * p;
+                }
+            }
+        }
+
+        let h = |i: usize| sys[i][8] / sys[i][i];
+        Some(Self {
+            m: [[h(0), h(1), h(2)], [h(3), h(4), h(5)], [h(6), h(7), 1.0]],
+        })
+    }
+
+    /// Return the Homography that undoes this one, if the Homography is
+    /// invertible.
+    pub fn inverse(&self) -> Option<Self> {
+        let m = &self.m;
+        let cofactor = |r0: usize, r1: usize, c0: usize, c1: usize| {
+            m[r0][c0] * m[r1][c1] - m[r0][c1] * m[r1][c0]
+        };
+
+        let adj = [
+            [
+                cofactor(1, 2, 1, 2),
+                -cofactor(0, 2, 1, 2),
+                cofactor(0, 1, 1, 2),
+            ],
+            [
+                -cofactor(1, 2, 0, 2),
+                cofactor(0, 2, 0, 2),
+                -cofactor(0, 1, 0, 2),
+            ],
+            [
+                cofactor(1, 2, 0, 1),
+                -cofactor(0, 2, 0, 1),
+                cofactor(0, 1, 0, 1),
+            ],
+        ];
+
+        let det = m[0][0] * adj[0][0] + m[0][1] * adj[1][0] + m[0][2] * adj[2][0];
+        if det.abs() < PRECISION_2 {
+            return None;
+        }
+
+        Some(Self {
+            m: adj.map(|row| row.map(|c| c / det)),
+        })
+    }
+}
+
+impl From<Xform> for Homography {
+    fn from(x: Xform) -> Self {
+        Self {
+            m: [
+                [x.a.x, x.b.x, x.c.x],
+                [x.a.y, x.b.y, x.c.y],
+                [0.0, 0.0, 1.0],
+            ],
+        }
+    }
 }
 
 impl<'a> MulAssign<&'a Xform> for Xform {
@@ -178,26 +425,74 @@ impl<'a> MulAssign<&'a Xform> for Geometry {
     }
 }
 
+impl<'a> MulAssign<&'a Xform> for Ellipse {
+    fn mul_assign(&mut self, rhs: &'a Xform) {
+        self.center *= rhs;
+        self.u = self.u.x * rhs.a + self.u.y * rhs.b;
+        self.v = self.v.x * rhs.a + self.v.y * rhs.b;
+    }
+}
+
+impl<'a> MulAssign<&'a Homography> for Homography {
+    fn mul_assign(&mut self, rhs: &'a Homography) {
+        let (a, b) = (&rhs.m, &self.m);
+        self.m = std::array::from_fn(|r| {
+            std::array::from_fn(|c| (0..3).map(|k| a[r][k] * b[k][c]).sum())
+        });
+    }
+}
+
+impl<'a> MulAssign<&'a Homography> for V {
+    fn mul_assign(&mut self, rhs: &'a Homography) {
+        let m = &rhs.m;
+        let w = m[2][0] * self.x + m[2][1] * self.y + m[2][2];
+        *self = v(
+            (m[0][0] * self.x + m[0][1] * self.y + m[0][2]) / w,
+            (m[1][0] * self.x + m[1][1] * self.y + m[1][2]) / w,
+        );
+    }
+}
+
+impl<'a> MulAssign<&'a Homography> for Path {
+    fn mul_assign(&mut self, rhs: &'a Homography) {
+        for p in self.iter_mut() {
+            *p *= rhs;
+        }
+    }
+}
+
+impl<'a> MulAssign<&'a Homography> for Geometry {
+    fn mul_assign(&mut self, rhs: &'a Homography) {
+        for p in &mut self.paths {
+            *p *= rhs;
+        }
+    }
+}
+
 macro_rules! impl_trivial_xform_helpers {
     ($t: ident) => {
-        impl MulAssign<Xform> for $t {
-            fn mul_assign(&mut self, rhs: Xform) {
+        impl_trivial_xform_helpers!(Xform, $t);
+    };
+
+    ($x: ident, $t: ident) => {
+        impl MulAssign<$x> for $t {
+            fn mul_assign(&mut self, rhs: $x) {
                 *self *= &rhs;
             }
         }
 
-        impl Mul<Xform> for $t {
+        impl Mul<$x> for $t {
             type Output = Self;
 
-            fn mul(self, rhs: Xform) -> Self::Output {
+            fn mul(self, rhs: $x) -> Self::Output {
                 self * &rhs
             }
         }
 
-        impl<'a> Mul<&'a Xform> for $t {
+        impl<'a> Mul<&'a $x> for $t {
             type Output = Self;
 
-            fn mul(mut self, rhs: &'a Xform) -> Self::Output {
+            fn mul(mut self, rhs: &'a $x) -> Self::Output {
                 self *= rhs;
                 self
             }
@@ -209,3 +504,9 @@ impl_trivial_xform_helpers!(Xform);
 impl_trivial_xform_helpers!(V);
 impl_trivial_xform_helpers!(Path);
 impl_trivial_xform_helpers!(Geometry);
+impl_trivial_xform_helpers!(Ellipse);
+
+impl_trivial_xform_helpers!(Homography, Homography);
+impl_trivial_xform_helpers!(Homography, V);
+impl_trivial_xform_helpers!(Homography, Path);
+impl_trivial_xform_helpers!(Homography, Geometry);